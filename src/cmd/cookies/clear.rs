@@ -0,0 +1,28 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{cookie::CookieJar, project::Project},
+};
+
+#[derive(Parser)]
+#[command(about = "Clear cookies of a project variable set")]
+pub struct ClearCommandHandler {
+    project: String,
+    variable: String,
+}
+
+#[async_trait]
+impl CommandHandler for ClearCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let mut project = Project::get(&self.project)?;
+        project.select_variable(&self.variable)?;
+
+        CookieJar::open(&project, &self.variable)?.clear()?;
+        println!("Cookies of {} cleared successfully", self.variable);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{cookie::CookieJar, project::Project},
+};
+
+#[derive(Parser)]
+#[command(about = "List cookies of a project variable set")]
+pub struct ListCommandHandler {
+    project: String,
+    variable: String,
+}
+
+#[async_trait]
+impl CommandHandler for ListCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let mut project = Project::get(&self.project)?;
+        project.select_variable(&self.variable)?;
+
+        let jar = CookieJar::open(&project, &self.variable)?;
+
+        println!("Cookies:\n");
+        jar.list()?.iter().for_each(|cookie| println!("{}", cookie));
+
+        Ok(())
+    }
+}
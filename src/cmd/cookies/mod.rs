@@ -0,0 +1,33 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+
+use self::{clear::ClearCommandHandler, list::ListCommandHandler};
+
+use super::CommandHandler;
+
+mod clear;
+mod list;
+
+#[derive(Parser)]
+#[command(about = "Manages the cookies saved by template runs")]
+pub struct CookiesCommandHandler {
+    #[command(subcommand)]
+    command: CookiesCommands,
+}
+
+#[derive(Subcommand)]
+pub enum CookiesCommands {
+    List(ListCommandHandler),
+    Clear(ClearCommandHandler),
+}
+
+#[async_trait]
+impl CommandHandler for CookiesCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        match &self.command {
+            CookiesCommands::List(handler) => handler.handle().await,
+            CookiesCommands::Clear(handler) => handler.handle().await,
+        }
+    }
+}
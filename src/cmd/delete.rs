@@ -1,11 +1,13 @@
 use async_trait::async_trait;
 use clap::Parser;
 
-use crate::http::HttpClient;
+use crate::http::{HttpClient, Method};
 use anyhow::Result;
 
 use super::{
-    shared::{ConfigHttpClient, HeaderConfigArgs, HttpClientRunner},
+    shared::{
+        ClientConfigArgs, ConfigHttpClient, HeaderConfigArgs, HttpClientRunner, OutputConfigArgs,
+    },
     CommandHandler,
 };
 
@@ -16,6 +18,12 @@ pub struct DeleteCommandHandler {
 
     #[command(flatten)]
     header_config: HeaderConfigArgs,
+
+    #[command(flatten)]
+    client_config: ClientConfigArgs,
+
+    #[command(flatten)]
+    output_config: OutputConfigArgs,
 }
 
 impl HttpClientRunner for DeleteCommandHandler {}
@@ -23,11 +31,20 @@ impl HttpClientRunner for DeleteCommandHandler {}
 #[async_trait]
 impl CommandHandler for DeleteCommandHandler {
     async fn handle(&self) -> Result<()> {
-        let mut client = HttpClient::delete(&self.url);
+        let client = self.client_config.build_client()?;
+        let mut client = HttpClient::new(&client, &Method::Delete, &self.url);
 
         client = self.header_config.config_http_client(client)?;
 
-        Self::run_http_client(client, self.header_config.verbose).await?;
+        match self.output_config.output_path(&self.url)? {
+            Some(path) => {
+                let resume = self.output_config.resume;
+                Self::download(client, &path, resume, self.header_config.verbose).await?;
+            }
+            None => {
+                Self::run_http_client(client, &self.header_config.log_options()).await?;
+            }
+        }
 
         Ok(())
     }
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{flow::Flow, project::Project},
+};
+
+#[derive(Parser)]
+#[command(about = "Create a flow of request templates")]
+pub struct CreateCommandHandler {
+    project: String,
+    flow: String,
+}
+
+#[async_trait]
+impl CommandHandler for CreateCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let project = Project::get(&self.project)?;
+        let flow = Flow::create(&project, &self.flow)?;
+
+        println!(
+            "Flow {} for project {} saved successfully",
+            flow.name, project.name
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{flow::Flow, project::Project},
+};
+
+#[derive(Parser)]
+#[command(about = "Delete a flow")]
+pub struct DeleteCommandHandler {
+    project: String,
+    flow: String,
+}
+
+#[async_trait]
+impl CommandHandler for DeleteCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let project = Project::get(&self.project)?;
+        let flow = Flow::get(&project, &self.flow)?;
+
+        flow.delete()?;
+        println!("Flow {} deleted successfully", self.flow);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{flow::Flow, project::Project},
+};
+
+#[derive(Parser)]
+#[command(about = "Edit a flow of request templates")]
+pub struct EditCommandHandler {
+    project: String,
+    flow: String,
+}
+
+#[async_trait]
+impl CommandHandler for EditCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let project = Project::get(&self.project)?;
+        let mut flow = Flow::get(&project, &self.flow)?;
+        let flow = flow.edit(&project)?.save()?;
+
+        println!(
+            "Flow {} from project {} saved successfully",
+            flow.name, project.name
+        );
+
+        Ok(())
+    }
+}
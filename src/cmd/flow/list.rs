@@ -0,0 +1,28 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{flow::Flow, project::Project},
+};
+
+#[derive(Parser)]
+#[command(about = "List flows of a project")]
+pub struct ListCommandHandler {
+    project: String,
+}
+
+#[async_trait]
+impl CommandHandler for ListCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let project = Project::get(&self.project)?;
+
+        println!("Flows:\n");
+        Flow::list(&project)?
+            .iter()
+            .for_each(|flow| println!("{}", flow));
+
+        Ok(())
+    }
+}
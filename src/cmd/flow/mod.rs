@@ -0,0 +1,45 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+
+use self::{
+    create::CreateCommandHandler, delete::DeleteCommandHandler, edit::EditCommandHandler,
+    list::ListCommandHandler, run::RunCommandHandler,
+};
+
+use super::CommandHandler;
+
+mod create;
+mod delete;
+mod edit;
+mod list;
+mod run;
+
+#[derive(Parser)]
+#[command(about = "Manages and runs template flows")]
+pub struct FlowCommandHandler {
+    #[command(subcommand)]
+    command: FlowCommands,
+}
+
+#[derive(Subcommand)]
+pub enum FlowCommands {
+    Create(CreateCommandHandler),
+    Edit(EditCommandHandler),
+    List(ListCommandHandler),
+    Delete(DeleteCommandHandler),
+    Run(Box<RunCommandHandler>),
+}
+
+#[async_trait]
+impl CommandHandler for FlowCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        match &self.command {
+            FlowCommands::Create(handler) => handler.handle().await,
+            FlowCommands::Edit(handler) => handler.handle().await,
+            FlowCommands::List(handler) => handler.handle().await,
+            FlowCommands::Delete(handler) => handler.handle().await,
+            FlowCommands::Run(handler) => handler.handle().await,
+        }
+    }
+}
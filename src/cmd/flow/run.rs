@@ -0,0 +1,116 @@
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+use reqwest::Client;
+
+use crate::{
+    cmd::{
+        shared::{ClientConfigArgs, HeaderConfigArgs, HttpClientRunner, TemplateRunner},
+        CommandHandler,
+    },
+    har::Har,
+    logger::OutputFormat,
+    template::{
+        cookie::CookieJar, flow::Flow, project::Project, settings::ProjectSettings, Template,
+    },
+};
+
+#[derive(Parser)]
+#[command(about = "Run the templates of a flow in order")]
+pub struct RunCommandHandler {
+    project: String,
+    variable: String,
+    flow: String,
+
+    #[arg(
+        short = 'y',
+        long = "no-edit",
+        visible_alias = "yes",
+        env = "REQ_NO_EDIT",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        help = "Send the requests without reviewing them in the editor"
+    )]
+    no_edit: bool,
+
+    #[arg(long, help = "Save the requests and responses to a har file")]
+    har: Option<PathBuf>,
+
+    #[arg(long, help = "Don't send or save the cookies of the variable set")]
+    no_cookies: bool,
+
+    #[command(flatten)]
+    header_config: HeaderConfigArgs,
+
+    #[command(flatten)]
+    client_config: ClientConfigArgs,
+}
+
+impl HttpClientRunner for RunCommandHandler {}
+impl TemplateRunner for RunCommandHandler {}
+
+#[async_trait]
+impl CommandHandler for RunCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let mut project = Project::get(&self.project)?;
+        project.select_variable(&self.variable)?;
+
+        let flow = Flow::get(&project, &self.flow)?;
+        let settings = ProjectSettings::get(&project)?.connection(&self.variable);
+        let jar = match self.no_cookies {
+            true => None,
+            false => Some(Arc::new(CookieJar::open(&project, &self.variable)?)),
+        };
+        let client = self
+            .client_config
+            .build_project_client(settings, jar.clone())?;
+
+        let mut har = self.har.as_ref().map(|_| Har::new());
+
+        let result = self.run_flow(&client, project, &flow, har.as_mut()).await;
+
+        if let (Some(har), Some(path)) = (&har, &self.har) {
+            har.save(path)?;
+        }
+
+        if let Some(jar) = jar {
+            jar.save()?;
+        }
+
+        result
+    }
+}
+
+impl RunCommandHandler {
+    async fn run_flow(
+        &self,
+        client: &Client,
+        mut project: Project,
+        flow: &Flow,
+        mut har: Option<&mut Har>,
+    ) -> Result<()> {
+        let total = flow.definition.templates.len();
+        for (index, template_name) in flow.definition.templates.iter().enumerate() {
+            if self.header_config.output_format == OutputFormat::Pretty {
+                println!("Running template {template_name} ({}/{total})", index + 1);
+            }
+
+            let mut template = Template::get(project, template_name)?;
+
+            Self::run_template(
+                client,
+                &mut template,
+                !self.no_edit,
+                &self.header_config,
+                None,
+                har.as_deref_mut(),
+            )
+            .await?;
+
+            project = template.project;
+        }
+
+        Ok(())
+    }
+}
@@ -2,17 +2,24 @@ use async_trait::async_trait;
 use clap::{Parser, Subcommand};
 
 use self::{
-    delete::DeleteCommandHandler, get::GetCommandHandler, patch::PatchCommandHandler,
-    post::PostCommandHandler, put::PutCommandHandler, run::RunCommandHandler,
-    template::TemplateCommandHandler,
+    cookies::CookiesCommandHandler, delete::DeleteCommandHandler, flow::FlowCommandHandler,
+    get::GetCommandHandler, head::HeadCommandHandler, options::OptionsCommandHandler,
+    patch::PatchCommandHandler, post::PostCommandHandler, put::PutCommandHandler,
+    request::RequestCommandHandler, run::RunCommandHandler, template::TemplateCommandHandler,
 };
+use crate::logger::OutputFormat;
 use anyhow::Result;
 
+mod cookies;
 mod delete;
+mod flow;
 mod get;
+mod head;
+mod options;
 mod patch;
 mod post;
 mod put;
+mod request;
 mod run;
 mod shared;
 mod template;
@@ -30,6 +37,15 @@ pub struct Cli {
 
     #[arg(global = true, short, long, help = "Print extra information")]
     pub verbose: bool,
+
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        default_value_t = OutputFormat::Pretty,
+        help = "How the response is printed"
+    )]
+    pub output_format: OutputFormat,
 }
 
 #[derive(Subcommand)]
@@ -39,6 +55,11 @@ pub enum Commands {
     Put(PutCommandHandler),
     Patch(PatchCommandHandler),
     Delete(DeleteCommandHandler),
+    Head(HeadCommandHandler),
+    Options(OptionsCommandHandler),
+    Request(RequestCommandHandler),
     Template(TemplateCommandHandler),
     Run(RunCommandHandler),
+    Flow(FlowCommandHandler),
+    Cookies(CookiesCommandHandler),
 }
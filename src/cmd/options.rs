@@ -0,0 +1,51 @@
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::http::{HttpClient, Method};
+use anyhow::Result;
+
+use super::{
+    shared::{
+        ClientConfigArgs, ConfigHttpClient, HeaderConfigArgs, HttpClientRunner, OutputConfigArgs,
+    },
+    CommandHandler,
+};
+
+#[derive(Parser)]
+#[command(about = "Executes an options request")]
+pub struct OptionsCommandHandler {
+    url: String,
+
+    #[command(flatten)]
+    header_config: HeaderConfigArgs,
+
+    #[command(flatten)]
+    client_config: ClientConfigArgs,
+
+    #[command(flatten)]
+    output_config: OutputConfigArgs,
+}
+
+impl HttpClientRunner for OptionsCommandHandler {}
+
+#[async_trait]
+impl CommandHandler for OptionsCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let client = self.client_config.build_client()?;
+        let mut client = HttpClient::new(&client, &Method::Options, &self.url);
+
+        client = self.header_config.config_http_client(client)?;
+
+        match self.output_config.output_path(&self.url)? {
+            Some(path) => {
+                let resume = self.output_config.resume;
+                Self::download(client, &path, resume, self.header_config.verbose).await?;
+            }
+            None => {
+                Self::run_http_client(client, &self.header_config.log_options()).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
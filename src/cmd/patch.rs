@@ -1,11 +1,14 @@
 use async_trait::async_trait;
 use clap::Parser;
 
-use crate::http::HttpClient;
+use crate::http::{HttpClient, Method};
 use anyhow::Result;
 
 use super::{
-    shared::{BodyConfigArgs, ConfigHttpClient, HeaderConfigArgs, HttpClientRunner},
+    shared::{
+        BodyConfigArgs, ClientConfigArgs, ConfigHttpClient, HeaderConfigArgs, HttpClientRunner,
+        OutputConfigArgs,
+    },
     CommandHandler,
 };
 
@@ -17,6 +20,12 @@ pub struct PatchCommandHandler {
     #[command(flatten)]
     header_config: HeaderConfigArgs,
 
+    #[command(flatten)]
+    client_config: ClientConfigArgs,
+
+    #[command(flatten)]
+    output_config: OutputConfigArgs,
+
     #[command(flatten)]
     body_config: BodyConfigArgs,
 }
@@ -26,12 +35,21 @@ impl HttpClientRunner for PatchCommandHandler {}
 #[async_trait]
 impl CommandHandler for PatchCommandHandler {
     async fn handle(&self) -> Result<()> {
-        let mut client = HttpClient::patch(&self.url);
+        let client = self.client_config.build_client()?;
+        let mut client = HttpClient::new(&client, &Method::Patch, &self.url);
 
         client = self.header_config.config_http_client(client)?;
         client = self.body_config.config_http_client(client)?;
 
-        Self::run_http_client(client, self.header_config.verbose).await?;
+        match self.output_config.output_path(&self.url)? {
+            Some(path) => {
+                let resume = self.output_config.resume;
+                Self::download(client, &path, resume, self.header_config.verbose).await?;
+            }
+            None => {
+                Self::run_http_client(client, &self.header_config.log_options()).await?;
+            }
+        }
 
         Ok(())
     }
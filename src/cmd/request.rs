@@ -0,0 +1,58 @@
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::http::{HttpClient, Method};
+use anyhow::Result;
+
+use super::{
+    shared::{
+        BodyConfigArgs, ClientConfigArgs, ConfigHttpClient, HeaderConfigArgs, HttpClientRunner,
+        OutputConfigArgs,
+    },
+    CommandHandler,
+};
+
+#[derive(Parser)]
+#[command(about = "Executes a request with any http method")]
+pub struct RequestCommandHandler {
+    #[arg(help = "Http method, Example: PROPFIND")]
+    method: Method,
+    url: String,
+
+    #[command(flatten)]
+    header_config: HeaderConfigArgs,
+
+    #[command(flatten)]
+    client_config: ClientConfigArgs,
+
+    #[command(flatten)]
+    output_config: OutputConfigArgs,
+
+    #[command(flatten)]
+    body_config: BodyConfigArgs,
+}
+
+impl HttpClientRunner for RequestCommandHandler {}
+
+#[async_trait]
+impl CommandHandler for RequestCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let client = self.client_config.build_client()?;
+        let mut client = HttpClient::new(&client, &self.method, &self.url);
+
+        client = self.header_config.config_http_client(client)?;
+        client = self.body_config.config_http_client(client)?;
+
+        match self.output_config.output_path(&self.url)? {
+            Some(path) => {
+                let resume = self.output_config.resume;
+                Self::download(client, &path, resume, self.header_config.verbose).await?;
+            }
+            None => {
+                Self::run_http_client(client, &self.header_config.log_options()).await?;
+            }
+        }
+
+        Ok(())
+    }
+}
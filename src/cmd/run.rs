@@ -1,14 +1,18 @@
+use std::{path::PathBuf, sync::Arc};
+
 use anyhow::Result;
 use async_trait::async_trait;
 use clap::Parser;
 
 use crate::{
     cmd::CommandHandler,
-    http::{HttpClient, Method},
-    template::{project::Project, Template},
+    har::Har,
+    template::{cookie::CookieJar, project::Project, settings::ProjectSettings, Template},
 };
 
-use super::shared::{HeaderConfigArgs, HttpClientRunner};
+use super::shared::{
+    ClientConfigArgs, HeaderConfigArgs, HttpClientRunner, OutputConfigArgs, TemplateRunner,
+};
 
 #[derive(Parser)]
 #[command(about = "Run request from a template")]
@@ -17,11 +21,34 @@ pub struct RunCommandHandler {
     variable: String,
     template: String,
 
+    #[arg(
+        short = 'y',
+        long = "no-edit",
+        visible_alias = "yes",
+        env = "REQ_NO_EDIT",
+        value_parser = clap::builder::BoolishValueParser::new(),
+        help = "Send the request without reviewing it in the editor"
+    )]
+    no_edit: bool,
+
+    #[arg(long, help = "Save the request and responses to a har file")]
+    har: Option<PathBuf>,
+
+    #[arg(long, help = "Don't send or save the cookies of the variable set")]
+    no_cookies: bool,
+
     #[command(flatten)]
     header_config: HeaderConfigArgs,
+
+    #[command(flatten)]
+    client_config: ClientConfigArgs,
+
+    #[command(flatten)]
+    output_config: OutputConfigArgs,
 }
 
 impl HttpClientRunner for RunCommandHandler {}
+impl TemplateRunner for RunCommandHandler {}
 
 #[async_trait]
 impl CommandHandler for RunCommandHandler {
@@ -29,26 +56,37 @@ impl CommandHandler for RunCommandHandler {
         let mut project = Project::get(&self.project)?;
         project.select_variable(&self.variable)?;
 
+        let settings = ProjectSettings::get(&project)?.connection(&self.variable);
+        let jar = match self.no_cookies {
+            true => None,
+            false => Some(Arc::new(CookieJar::open(&project, &self.variable)?)),
+        };
+
         let mut template = Template::get(project, &self.template)?;
 
-        let request = template.request_with_variables()?;
+        let client = self
+            .client_config
+            .build_project_client(settings, jar.clone())?;
+        let mut har = self.har.as_ref().map(|_| Har::new());
 
-        let mut client = match request.method {
-            Method::Get => HttpClient::get(&request.url),
-            Method::Post => HttpClient::post(&request.url).with_body_from_value(request.body)?,
-            Method::Patch => HttpClient::patch(&request.url).with_body_from_value(request.body)?,
-            Method::Put => HttpClient::put(&request.url).with_body_from_value(request.body)?,
-            Method::Delete => HttpClient::delete(&request.url),
-        };
+        let result = Self::run_template(
+            &client,
+            &mut template,
+            !self.no_edit,
+            &self.header_config,
+            Some(&self.output_config),
+            har.as_mut(),
+        )
+        .await;
 
-        if !request.headers.is_empty() {
-            client = client.with_headers_from_hash(request.headers);
+        if let (Some(har), Some(path)) = (&har, &self.har) {
+            har.save(path)?;
         }
 
-        let response_string = Self::run_http_client(client, self.header_config.verbose).await?;
+        if let Some(jar) = jar {
+            jar.save()?;
+        }
 
-        template
-            .project
-            .update_variables_from_response_body(&response_string)
+        result
     }
 }
@@ -26,7 +26,9 @@ impl CommandHandler for CreateCommandHandler {
         if let Some(new_project) = &self.new_project {
             let project = Project::create(new_project.to_string())?;
 
-            return Ok(println!("Project {} created", project.name));
+            println!("Project {} created", project.name);
+
+            return Ok(());
         }
 
         let project_name = self.project.as_ref().ok_or(anyhow!(
@@ -38,10 +40,9 @@ impl CommandHandler for CreateCommandHandler {
         if let Some(variable) = &self.variable {
             project.create_variable(variable)?;
 
-            return Ok(println!(
-                "Variable {} created for project {}",
-                variable, project.name
-            ));
+            println!("Variable {} created for project {}", variable, project.name);
+
+            return Ok(());
         }
 
         let template_name = self
@@ -50,9 +51,11 @@ impl CommandHandler for CreateCommandHandler {
             .ok_or(anyhow!("Template name is required for creation"))?;
         let template = Template::create(project, template_name)?;
 
-        Ok(println!(
+        println!(
             "Template {} for project {} saved successfully",
             template.name, template.project.name
-        ))
+        );
+
+        Ok(())
     }
 }
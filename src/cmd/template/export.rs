@@ -0,0 +1,52 @@
+use std::mem;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{
+        export::{self, ExportFormat},
+        project::Project,
+        Template,
+    },
+};
+
+#[derive(Parser)]
+#[command(about = "Export a request template as a shell command")]
+pub struct ExportCommandHandler {
+    project: String,
+    template: String,
+
+    #[arg(long, value_enum, default_value = "curl", help = "Command format")]
+    format: ExportFormat,
+
+    #[arg(long, help = "Variable used to replace the template variables")]
+    variable: Option<String>,
+}
+
+#[async_trait]
+impl CommandHandler for ExportCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let mut project = Project::get(&self.project)?;
+        if let Some(variable) = &self.variable {
+            project.select_variable(variable)?;
+        }
+
+        let mut template = Template::get(project, &self.template)?;
+        let mut request = match &self.variable {
+            Some(_) => template.request_with_variables(false)?,
+            None => mem::take(&mut template.request),
+        };
+
+        request.resolve_file_paths(&template.project.path);
+        if let Some(path) = request.body_file.take() {
+            request.body = Some(template.read_body_file(&path, self.variable.is_some())?);
+        }
+
+        println!("{}", export::render(&request, self.format)?);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use std::io::{self, Read};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{import::curl, project::Project, Template},
+};
+
+#[derive(Parser)]
+#[command(about = "Import a request template from a curl command")]
+pub struct CurlCommandHandler {
+    project: String,
+    template: String,
+
+    #[arg(help = "Curl command, read from stdin when omitted")]
+    command: Option<String>,
+}
+
+#[async_trait]
+impl CommandHandler for CurlCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let command = match &self.command {
+            Some(command) => command.to_string(),
+            None => {
+                let mut command = String::new();
+                io::stdin().read_to_string(&mut command)?;
+
+                command
+            }
+        };
+
+        let project = Project::get(&self.project)?;
+        let mut template = Template::new(project, &self.template);
+        template.request = curl::parse(&command)?;
+        template.save()?;
+
+        println!(
+            "Template {} for project {} imported successfully",
+            template.name, template.project.name
+        );
+
+        Ok(())
+    }
+}
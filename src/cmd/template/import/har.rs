@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{cmd::CommandHandler, template::import::har};
+
+use super::log_import_report;
+
+#[derive(Parser)]
+#[command(about = "Import the requests of a har file as templates")]
+pub struct HarCommandHandler {
+    #[arg(help = "Har 1.2 file exported from a browser")]
+    har: PathBuf,
+
+    #[arg(long, help = "Project name to import the templates into")]
+    project: String,
+}
+
+#[async_trait]
+impl CommandHandler for HarCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let report = har::import(&self.har, &self.project)?;
+
+        log_import_report(&report);
+
+        Ok(())
+    }
+}
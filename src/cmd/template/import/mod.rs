@@ -0,0 +1,60 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::{Parser, Subcommand};
+
+use self::{
+    curl::CurlCommandHandler, har::HarCommandHandler, openapi::OpenapiCommandHandler,
+    postman::PostmanCommandHandler,
+};
+
+use crate::{cmd::CommandHandler, template::import::ImportReport};
+
+mod curl;
+mod har;
+mod openapi;
+mod postman;
+
+#[derive(Parser)]
+#[command(about = "Import request templates from other tools")]
+pub struct ImportCommandHandler {
+    #[command(subcommand)]
+    command: ImportCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ImportCommands {
+    Curl(CurlCommandHandler),
+    Postman(PostmanCommandHandler),
+    Openapi(OpenapiCommandHandler),
+    Har(HarCommandHandler),
+}
+
+#[async_trait]
+impl CommandHandler for ImportCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        match &self.command {
+            ImportCommands::Curl(handler) => handler.handle().await,
+            ImportCommands::Postman(handler) => handler.handle().await,
+            ImportCommands::Openapi(handler) => handler.handle().await,
+            ImportCommands::Har(handler) => handler.handle().await,
+        }
+    }
+}
+
+fn log_import_report(report: &ImportReport) {
+    println!(
+        "Imported {} templates into project {}",
+        report.templates.len(),
+        report.project
+    );
+
+    if report.warnings.is_empty() {
+        return;
+    }
+
+    println!("\nNot converted:\n");
+    report
+        .warnings
+        .iter()
+        .for_each(|warning| println!("{warning}"));
+}
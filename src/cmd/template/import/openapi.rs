@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{cmd::CommandHandler, template::import::openapi};
+
+use super::log_import_report;
+
+#[derive(Parser)]
+#[command(about = "Import an openapi 3 spec with a template per operation")]
+pub struct OpenapiCommandHandler {
+    #[arg(help = "Openapi 3 spec file in json or yaml")]
+    spec: PathBuf,
+
+    #[arg(long, help = "Project name to import the templates into")]
+    project: String,
+}
+
+#[async_trait]
+impl CommandHandler for OpenapiCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let report = openapi::import(&self.spec, &self.project)?;
+
+        log_import_report(&report);
+
+        Ok(())
+    }
+}
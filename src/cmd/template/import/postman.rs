@@ -0,0 +1,41 @@
+use std::path::PathBuf;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{cmd::CommandHandler, template::import::postman};
+
+use super::log_import_report;
+
+#[derive(Parser)]
+#[command(about = "Import a postman v2.1 collection as a project")]
+pub struct PostmanCommandHandler {
+    collection: PathBuf,
+
+    #[arg(long, help = "Project name, defaults to the collection name")]
+    project: Option<String>,
+
+    #[arg(
+        short,
+        long = "environment",
+        help = "Postman environment file to import as a variable",
+        action = clap::ArgAction::Append
+    )]
+    environments: Vec<PathBuf>,
+}
+
+#[async_trait]
+impl CommandHandler for PostmanCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let report = postman::import(
+            &self.collection,
+            self.project.as_deref(),
+            &self.environments,
+        )?;
+
+        log_import_report(&report);
+
+        Ok(())
+    }
+}
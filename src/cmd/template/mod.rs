@@ -4,7 +4,9 @@ use clap::{Parser, Subcommand};
 
 use self::{
     create::CreateCommandHandler, delete::DeleteCommandHandler, edit::EditCommandHandler,
-    list::ListCommandHandler, relocate::RelocateCommandHandler, rename::RenameCommandHandler,
+    export::ExportCommandHandler, import::ImportCommandHandler, list::ListCommandHandler,
+    relocate::RelocateCommandHandler, rename::RenameCommandHandler,
+    settings::SettingsCommandHandler,
 };
 
 use super::CommandHandler;
@@ -12,9 +14,12 @@ use super::CommandHandler;
 mod create;
 mod delete;
 mod edit;
+mod export;
+mod import;
 mod list;
 mod relocate;
 mod rename;
+mod settings;
 
 #[derive(Parser)]
 #[command(about = "Manages request templates")]
@@ -31,6 +36,9 @@ pub enum TemplateCommands {
     Delete(DeleteCommandHandler),
     Rename(RenameCommandHandler),
     Move(RelocateCommandHandler),
+    Import(ImportCommandHandler),
+    Export(ExportCommandHandler),
+    Settings(SettingsCommandHandler),
 }
 
 #[async_trait]
@@ -43,6 +51,9 @@ impl CommandHandler for TemplateCommandHandler {
             TemplateCommands::Delete(handler) => handler.handle().await,
             TemplateCommands::Rename(handler) => handler.handle().await,
             TemplateCommands::Move(handler) => handler.handle().await,
+            TemplateCommands::Import(handler) => handler.handle().await,
+            TemplateCommands::Export(handler) => handler.handle().await,
+            TemplateCommands::Settings(handler) => handler.handle().await,
         }
     }
 }
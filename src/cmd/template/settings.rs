@@ -0,0 +1,26 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use clap::Parser;
+
+use crate::{
+    cmd::CommandHandler,
+    template::{project::Project, settings::ProjectSettings},
+};
+
+#[derive(Parser)]
+#[command(about = "Edit the settings shared by every template of a project")]
+pub struct SettingsCommandHandler {
+    project: String,
+}
+
+#[async_trait]
+impl CommandHandler for SettingsCommandHandler {
+    async fn handle(&self) -> Result<()> {
+        let project = Project::get(&self.project)?;
+        ProjectSettings::get(&project)?.edit()?.save()?;
+
+        println!("Settings of project {} saved successfully", project.name);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,291 @@
+use std::{fs::OpenOptions, path::Path, time::Duration};
+
+use anyhow::{Context, Result};
+use reqwest::{header::HeaderMap, Request, Version};
+use serde::{Deserialize, Serialize};
+
+use crate::http::HttpResponse;
+
+#[derive(Serialize, Deserialize)]
+pub struct Har {
+    pub log: Log,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Log {
+    pub version: String,
+    pub creator: Creator,
+    pub entries: Vec<Entry>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Creator {
+    pub name: String,
+    pub version: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Entry {
+    pub started_date_time: String,
+    pub time: f64,
+    pub request: HarRequest,
+    pub response: HarResponse,
+
+    #[serde(default)]
+    pub cache: Cache,
+
+    #[serde(default)]
+    pub timings: Timings,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarRequest {
+    pub method: String,
+    pub url: String,
+
+    #[serde(default)]
+    pub http_version: String,
+
+    #[serde(default)]
+    pub cookies: Vec<NameValue>,
+
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+
+    #[serde(default)]
+    pub query_string: Vec<NameValue>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_data: Option<PostData>,
+
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HarResponse {
+    pub status: u16,
+
+    #[serde(default)]
+    pub status_text: String,
+
+    #[serde(default)]
+    pub http_version: String,
+
+    #[serde(default)]
+    pub cookies: Vec<NameValue>,
+
+    #[serde(default)]
+    pub headers: Vec<NameValue>,
+
+    pub content: Content,
+
+    #[serde(default, rename = "redirectURL")]
+    pub redirect_url: String,
+
+    #[serde(default = "unknown_size")]
+    pub headers_size: i64,
+
+    #[serde(default = "unknown_size")]
+    pub body_size: i64,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct NameValue {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PostData {
+    #[serde(default)]
+    pub mime_type: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub params: Vec<NameValue>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Content {
+    #[serde(default)]
+    pub size: i64,
+
+    #[serde(default)]
+    pub mime_type: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub text: Option<String>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Cache {}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Timings {
+    #[serde(default = "unknown_time")]
+    pub blocked: f64,
+
+    #[serde(default = "unknown_time")]
+    pub dns: f64,
+
+    #[serde(default = "unknown_time")]
+    pub connect: f64,
+
+    pub send: f64,
+    pub wait: f64,
+    pub receive: f64,
+
+    #[serde(default = "unknown_time")]
+    pub ssl: f64,
+}
+
+fn unknown_size() -> i64 {
+    -1
+}
+
+fn unknown_time() -> f64 {
+    -1.0
+}
+
+impl Har {
+    pub fn new() -> Self {
+        Self {
+            log: Log {
+                version: "1.2".into(),
+                creator: Creator {
+                    name: env!("CARGO_PKG_NAME").into(),
+                    version: env!("CARGO_PKG_VERSION").into(),
+                },
+                entries: vec![],
+            },
+        }
+    }
+
+    pub fn add_entry(&mut self, req: &Request, res: &HttpResponse) {
+        let request_body = req
+            .body()
+            .and_then(|b| b.as_bytes())
+            .map(|b| String::from_utf8_lossy(b).to_string());
+
+        let request = HarRequest {
+            method: req.method().to_string(),
+            url: req.url().to_string(),
+            http_version: http_version(req.version()),
+            cookies: vec![],
+            headers: name_values(req.headers()),
+            query_string: req
+                .url()
+                .query_pairs()
+                .map(|(name, value)| NameValue {
+                    name: name.to_string(),
+                    value: value.to_string(),
+                })
+                .collect(),
+            body_size: request_body.as_ref().map_or(0, |b| b.len() as i64),
+            post_data: request_body.map(|text| PostData {
+                mime_type: content_type(req.headers()),
+                text: Some(text),
+                params: vec![],
+            }),
+            headers_size: -1,
+        };
+
+        let response = HarResponse {
+            status: res.status.as_u16(),
+            status_text: res.status.canonical_reason().unwrap_or_default().into(),
+            http_version: http_version(res.version),
+            cookies: vec![],
+            headers: name_values(&res.headers),
+            content: Content {
+                size: res.body.len() as i64,
+                mime_type: content_type(&res.headers),
+                text: Some(res.body.to_string()),
+            },
+            redirect_url: res
+                .headers
+                .get("Location")
+                .and_then(|l| l.to_str().ok())
+                .unwrap_or_default()
+                .into(),
+            headers_size: -1,
+            body_size: res.body.len() as i64,
+        };
+
+        // The entry covers the final attempt, redirects and retries before it are
+        // left out. The tls handshake is part of connect as the spec asks
+        let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let phase = |duration: Option<Duration>| duration.map_or(-1.0, millis);
+        let connection = &res.connection;
+        let connecting = [connection.dns, connection.connect, connection.tls]
+            .into_iter()
+            .flatten()
+            .sum();
+        let started = res.started_at
+            + chrono::Duration::from_std(res.blocked).unwrap_or_else(|_| chrono::Duration::zero());
+        let time = millis(res.elapsed.saturating_sub(res.blocked));
+        let wait = millis(res.waiting.saturating_sub(connecting));
+
+        self.log.entries.push(Entry {
+            started_date_time: started.to_rfc3339(),
+            time,
+            request,
+            response,
+            cache: Cache {},
+            timings: Timings {
+                blocked: -1.0,
+                dns: phase(connection.dns),
+                connect: match connection.connect {
+                    Some(connect) => millis(connect + connection.tls.unwrap_or_default()),
+                    None => -1.0,
+                },
+                send: 0.0,
+                wait,
+                receive: millis(res.elapsed.saturating_sub(res.blocked + res.waiting)),
+                ssl: phase(connection.tls),
+            },
+        });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(path)?;
+
+        serde_json::to_writer_pretty(file, self).context("Failed to save har file")
+    }
+}
+
+fn name_values(headers: &HeaderMap) -> Vec<NameValue> {
+    headers
+        .iter()
+        .map(|(k, v)| NameValue {
+            name: k.to_string(),
+            value: String::from_utf8_lossy(v.as_bytes()).to_string(),
+        })
+        .collect()
+}
+
+fn content_type(headers: &HeaderMap) -> String {
+    headers
+        .get("Content-Type")
+        .and_then(|c| c.to_str().ok())
+        .unwrap_or_default()
+        .to_string()
+}
+
+fn http_version(version: Version) -> String {
+    format!("{version:?}")
+}
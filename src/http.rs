@@ -1,96 +1,766 @@
-use std::{collections::HashMap, str::FromStr, time::Duration};
+use std::{
+    collections::HashMap,
+    env,
+    fmt::{self, Display, Formatter},
+    fs,
+    net::{IpAddr, SocketAddr},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use anyhow::{anyhow, Context, Result};
-use reqwest::{Client, Request, RequestBuilder, Response};
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use reqwest::{
+    cookie::CookieStore,
+    header::{
+        HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, COOKIE,
+        LOCATION, PROXY_AUTHORIZATION, RANGE, RETRY_AFTER, TRANSFER_ENCODING,
+    },
+    multipart::{Form, Part},
+    redirect::Policy,
+    tls, Certificate, Client, ClientBuilder, Identity, NoProxy, Proxy, Request, RequestBuilder,
+    Response, StatusCode, Url, Version,
+};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use tokio::time::sleep;
 
-#[derive(Debug, Serialize, Deserialize)]
+use crate::{
+    logger,
+    timing::{self, ConnectionTiming},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
 pub enum Method {
-    #[serde(rename = "GET")]
     Get,
-
-    #[serde(rename = "POST")]
     Post,
-
-    #[serde(rename = "PATCH")]
     Patch,
-
-    #[serde(rename = "PUT")]
     Put,
-
-    #[serde(rename = "DELETE")]
     Delete,
+    Head,
+    Options,
+    Other(reqwest::Method),
 }
 
 impl FromStr for Method {
     type Err = anyhow::Error;
 
     fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
-        match s {
+        let method = s.trim().to_uppercase();
+
+        match method.as_str() {
             "GET" => Ok(Method::Get),
             "POST" => Ok(Method::Post),
             "PATCH" => Ok(Method::Patch),
             "PUT" => Ok(Method::Put),
             "DELETE" => Ok(Method::Delete),
-            _ => Err(anyhow!("Invalid http method")),
+            "HEAD" => Ok(Method::Head),
+            "OPTIONS" => Ok(Method::Options),
+            _ => reqwest::Method::from_bytes(method.as_bytes())
+                .map(Method::Other)
+                .map_err(|_| anyhow!("Invalid http method {s}")),
+        }
+    }
+}
+
+impl TryFrom<String> for Method {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Method> for String {
+    fn from(method: Method) -> Self {
+        method.to_string()
+    }
+}
+
+impl Display for Method {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let method = match self {
+            Method::Get => "GET",
+            Method::Post => "POST",
+            Method::Patch => "PATCH",
+            Method::Put => "PUT",
+            Method::Delete => "DELETE",
+            Method::Head => "HEAD",
+            Method::Options => "OPTIONS",
+            Method::Other(m) => m.as_str(),
+        };
+
+        write!(f, "{method}")
+    }
+}
+
+impl From<&Method> for reqwest::Method {
+    fn from(method: &Method) -> Self {
+        match method {
+            Method::Get => reqwest::Method::GET,
+            Method::Post => reqwest::Method::POST,
+            Method::Patch => reqwest::Method::PATCH,
+            Method::Put => reqwest::Method::PUT,
+            Method::Delete => reqwest::Method::DELETE,
+            Method::Head => reqwest::Method::HEAD,
+            Method::Options => reqwest::Method::OPTIONS,
+            Method::Other(m) => m.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct FormPart {
+    pub name: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+
+    #[serde(default, rename = "type", skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+impl FromStr for FormPart {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let (name, value) = s.split_once('=').ok_or(anyhow!(
+            "Invalid form field format, must be 'name=value' or 'name=@path'"
+        ))?;
+
+        let file = match value.strip_prefix('@') {
+            Some(file) => file,
+            None => {
+                return Ok(Self {
+                    name: name.to_string(),
+                    value: Some(value.to_string()),
+                    file: None,
+                    content_type: None,
+                })
+            }
+        };
+
+        let mut options = file.split(';');
+        let path = options.next().unwrap_or_default();
+        let content_type = options
+            .flat_map(|o| o.trim().strip_prefix("type="))
+            .map(|t| t.to_string())
+            .next();
+
+        Ok(Self {
+            name: name.to_string(),
+            value: None,
+            file: Some(PathBuf::from(path)),
+            content_type,
+        })
+    }
+}
+
+pub struct HttpResponse {
+    pub status: StatusCode,
+    pub version: Version,
+    pub headers: HeaderMap,
+    pub body: String,
+    pub started_at: DateTime<Utc>,
+    pub blocked: Duration,
+    pub waiting: Duration,
+    pub elapsed: Duration,
+    pub connection: ConnectionTiming,
+    pub redirects: Vec<Redirect>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ProxySettings {
+    pub url: String,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub username: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_proxy: Option<String>,
+}
+
+impl ProxySettings {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.into(),
+            username: None,
+            password: None,
+            no_proxy: None,
+        }
+    }
+}
+
+// native-tls can't enforce 1.3 as a minimum, it is still negotiated when both sides support it
+#[derive(Clone, Copy, ValueEnum, Serialize, Deserialize)]
+pub enum TlsVersion {
+    #[value(name = "1.0")]
+    #[serde(rename = "1.0")]
+    Tls10,
+    #[value(name = "1.1")]
+    #[serde(rename = "1.1")]
+    Tls11,
+    #[value(name = "1.2")]
+    #[serde(rename = "1.2")]
+    Tls12,
+}
+
+impl From<TlsVersion> for tls::Version {
+    fn from(version: TlsVersion) -> Self {
+        match version {
+            TlsVersion::Tls10 => tls::Version::TLS_1_0,
+            TlsVersion::Tls11 => tls::Version::TLS_1_1,
+            TlsVersion::Tls12 => tls::Version::TLS_1_2,
+        }
+    }
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct TlsSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cacert: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cert_password: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub insecure: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_version: Option<TlsVersion>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub resolve: Vec<String>,
+}
+
+impl TlsSettings {
+    pub fn resolve_file_paths(&mut self, base: &Path) {
+        for path in [&mut self.cacert, &mut self.cert, &mut self.key]
+            .into_iter()
+            .flatten()
+        {
+            *path = base.join(&*path);
+        }
+    }
+}
+
+pub struct HttpClientConfig {
+    builder: ClientBuilder,
+}
+
+impl HttpClientConfig {
+    // Redirects are followed by HttpClient::send, so every hop can be reported
+    pub fn new() -> Self {
+        Self {
+            builder: Client::builder()
+                .redirect(Policy::none())
+                .dns_resolver(timing::resolver()),
+        }
+    }
+
+    pub fn with_pool_max_idle(mut self, max: usize) -> Self {
+        self.builder = self.builder.pool_max_idle_per_host(max);
+
+        self
+    }
+
+    pub fn with_keep_alive(mut self, duration: Duration) -> Self {
+        self.builder = self
+            .builder
+            .pool_idle_timeout(duration)
+            .tcp_keepalive(duration);
+
+        self
+    }
+
+    pub fn with_http2_prior_knowledge(mut self) -> Self {
+        self.builder = self.builder.http2_prior_knowledge();
+
+        self
+    }
+
+    // Hosts in NO_PROXY skip the proxy unless the settings have their own list
+    pub fn with_proxy(mut self, settings: &ProxySettings) -> Result<Self> {
+        let proxy = Proxy::all(&settings.url).context(format!("Invalid proxy {}", settings.url))?;
+        self.builder = self.builder.proxy(configure_proxy(proxy, settings));
+
+        Ok(self)
+    }
+
+    // reqwest applies only the environment's NO_PROXY to HTTP_PROXY and HTTPS_PROXY,
+    // so they are rebuilt to take a no proxy list or credentials, the url of the
+    // settings is ignored
+    pub fn with_env_proxy(mut self, settings: &ProxySettings) -> Result<Self> {
+        // A client can set HTTP_PROXY through the Proxy header under CGI, reqwest ignores it too
+        let http = match env::var_os("REQUEST_METHOD") {
+            Some(_) => None,
+            None => env_proxy("HTTP_PROXY"),
+        };
+        let https = env_proxy("HTTPS_PROXY");
+
+        if http.is_none() && https.is_none() {
+            return Err(anyhow!(
+                "No proxy to apply --no-proxy or --proxy-user to, set --proxy, a project proxy or HTTP(S)_PROXY"
+            ));
         }
+
+        if let Some(url) = http {
+            let proxy = Proxy::http(&url).context(format!("Invalid HTTP_PROXY {url}"))?;
+            self.builder = self.builder.proxy(configure_proxy(proxy, settings));
+        }
+
+        if let Some(url) = https {
+            let proxy = Proxy::https(&url).context(format!("Invalid HTTPS_PROXY {url}"))?;
+            self.builder = self.builder.proxy(configure_proxy(proxy, settings));
+        }
+
+        Ok(self)
+    }
+
+    pub fn with_tls(mut self, settings: &TlsSettings) -> Result<Self> {
+        if let Some(path) = &settings.cacert {
+            let pem = fs::read(path).context(format!("Failed to read {}", path.display()))?;
+            for cert in pem_certificates(&pem) {
+                self.builder = self
+                    .builder
+                    .add_root_certificate(Certificate::from_pem(&cert)?);
+            }
+        }
+
+        if let Some(path) = &settings.cert {
+            let cert = fs::read(path).context(format!("Failed to read {}", path.display()))?;
+
+            // PEM certificates need a PKCS#8 key, anything else is read as PKCS#12
+            let identity = match cert.starts_with(b"-----BEGIN") {
+                true => {
+                    let key = match &settings.key {
+                        Some(key) => {
+                            fs::read(key).context(format!("Failed to read {}", key.display()))?
+                        }
+                        None => cert.clone(),
+                    };
+                    Identity::from_pkcs8_pem(&cert, &key)
+                }
+                false => Identity::from_pkcs12_der(
+                    &cert,
+                    settings.cert_password.as_deref().unwrap_or_default(),
+                ),
+            }
+            .context(format!("Invalid client certificate {}", path.display()))?;
+
+            self.builder = self.builder.identity(identity);
+        }
+
+        if settings.insecure == Some(true) {
+            self.builder = self
+                .builder
+                .danger_accept_invalid_certs(true)
+                .danger_accept_invalid_hostnames(true);
+        }
+
+        if let Some(version) = settings.min_version {
+            self.builder = self.builder.min_tls_version(version.into());
+        }
+
+        // host:port:address connects to the address while the host is kept for SNI
+        for resolve in &settings.resolve {
+            let mut parts = resolve.splitn(3, ':');
+            let (Some(host), Some(port), Some(address)) =
+                (parts.next(), parts.next(), parts.next())
+            else {
+                return Err(anyhow!(
+                    "Invalid resolve {resolve}, expected host:port:address"
+                ));
+            };
+            let address: IpAddr = address
+                .trim_matches(|c| c == '[' || c == ']')
+                .parse()
+                .context(format!("Invalid address in resolve {resolve}"))?;
+            let port: u16 = port
+                .parse()
+                .context(format!("Invalid port in resolve {resolve}"))?;
+
+            self.builder = self.builder.resolve(host, SocketAddr::new(address, port));
+        }
+
+        Ok(self)
+    }
+
+    pub fn without_proxy(mut self) -> Self {
+        self.builder = self.builder.no_proxy();
+
+        self
+    }
+
+    pub fn with_cookie_provider<C: CookieStore + 'static>(mut self, provider: Arc<C>) -> Self {
+        self.builder = self.builder.cookie_provider(provider);
+
+        self
+    }
+
+    pub fn build(self) -> Result<Client> {
+        self.builder.build().context("Failed to build http client")
     }
 }
 
 #[derive(Debug)]
+pub struct Redirect {
+    pub status: StatusCode,
+    pub method: reqwest::Method,
+    pub url: Url,
+    pub location: Url,
+    pub next_method: reqwest::Method,
+    pub headers: HeaderMap,
+}
+
+// Redirects and retries before the final attempt don't count towards its timing
+pub struct Attempt {
+    pub started: Instant,
+    pub connection: ConnectionTiming,
+}
+
+fn configure_proxy(mut proxy: Proxy, settings: &ProxySettings) -> Proxy {
+    if let Some(username) = &settings.username {
+        proxy = proxy.basic_auth(username, settings.password.as_deref().unwrap_or_default());
+    }
+
+    let no_proxy = match &settings.no_proxy {
+        Some(hosts) => NoProxy::from_string(hosts),
+        None => NoProxy::from_env(),
+    };
+
+    proxy.no_proxy(no_proxy)
+}
+
+fn env_proxy(name: &str) -> Option<String> {
+    [name.to_string(), name.to_lowercase()]
+        .into_iter()
+        .find_map(|name| env::var(name).ok())
+        .filter(|url| !url.trim().is_empty())
+}
+
+// Splits on the first colon and trims only the edges, so values keep their spaces
+// and colons, 'Name;' is a header with an empty value
+pub fn parse_header(header: &str) -> Result<(&str, &str)> {
+    let (name, value) = match (header.split_once(':'), header.trim().strip_suffix(';')) {
+        (Some((name, value)), _) => (name.trim(), value.trim()),
+        (None, Some(name)) => (name.trim(), ""),
+        _ => {
+            return Err(anyhow!(
+                "Invalid header {header}, must be 'Name: Value' or 'Name;'"
+            ))
+        }
+    };
+
+    if name.is_empty() {
+        return Err(anyhow!("Invalid header {header}, the name is empty"));
+    }
+
+    Ok((name, value))
+}
+
+// Retry-After is either a number of seconds or an http date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(seconds) = value.parse() {
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let at = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (at.with_timezone(&Utc) - Utc::now())
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
+fn pem_certificates(pem: &[u8]) -> Vec<Vec<u8>> {
+    const END: &str = "-----END CERTIFICATE-----";
+
+    String::from_utf8_lossy(pem)
+        .split_inclusive(END)
+        .filter(|cert| cert.contains(END))
+        .map(|cert| cert.trim().as_bytes().to_vec())
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum RetryOn {
+    Status(u16),
+    Connect,
+    Timeout,
+}
+
+impl FromStr for RetryOn {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "connect" => Ok(RetryOn::Connect),
+            "timeout" => Ok(RetryOn::Timeout),
+            status => status
+                .parse()
+                .ok()
+                .filter(|status| (100..600).contains(status))
+                .map(RetryOn::Status)
+                .ok_or(anyhow!(
+                    "Invalid retry condition {s}, expected a status code, connect or timeout"
+                )),
+        }
+    }
+}
+
+impl TryFrom<String> for RetryOn {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> Result<Self> {
+        value.parse()
+    }
+}
+
+impl Display for RetryOn {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryOn::Status(status) => write!(f, "{status}"),
+            RetryOn::Connect => write!(f, "connect"),
+            RetryOn::Timeout => write!(f, "timeout"),
+        }
+    }
+}
+
+impl From<RetryOn> for String {
+    fn from(value: RetryOn) -> Self {
+        value.to_string()
+    }
+}
+
 pub struct HttpClient {
     req: RequestBuilder,
+    content_type: Option<String>,
+    follow_redirects: bool,
+    max_redirects: usize,
+    retry: Retry,
+}
+
+struct Retry {
+    count: u32,
+    delay: Duration,
+    max_delay: Duration,
+    on: Vec<RetryOn>,
+    verbose: bool,
+}
+
+impl Retry {
+    // Waits twice as long after every attempt with up to half of the delay added
+    // as jitter, a Retry-After from the server replaces the delay, neither waits
+    // longer than the max delay
+    async fn execute(&self, client: &Client, mut req: Request) -> Result<(Response, Attempt)> {
+        let mut attempt = 0;
+        loop {
+            attempt += 1;
+            let retry_req = match attempt <= self.count {
+                true => req.try_clone(),
+                false => None,
+            };
+
+            if attempt == 1 && self.count > 0 && retry_req.is_none() {
+                logger::log_retries_disabled(self.verbose);
+            }
+
+            timing::reset();
+            let started = Instant::now();
+            let https = req.url().scheme() == "https";
+            let result = client.execute(req).await;
+            let this_attempt = Attempt {
+                started,
+                connection: timing::connection(https),
+            };
+            let Some(retry_req) = retry_req else {
+                return Ok((result.context("Failed to execute request")?, this_attempt));
+            };
+
+            let (reason, retry_after) = match &result {
+                Ok(res) if self.on.contains(&RetryOn::Status(res.status().as_u16())) => {
+                    (res.status().to_string(), retry_after(res.headers()))
+                }
+                Err(e) if e.is_connect() && self.on.contains(&RetryOn::Connect) => {
+                    ("a connect error".to_string(), None)
+                }
+                Err(e) if e.is_timeout() && self.on.contains(&RetryOn::Timeout) => {
+                    ("a timeout".to_string(), None)
+                }
+                _ => return Ok((result.context("Failed to execute request")?, this_attempt)),
+            };
+
+            let delay = retry_after
+                .unwrap_or_else(|| {
+                    let backoff = 2u32
+                        .checked_pow(attempt - 1)
+                        .and_then(|factor| self.delay.checked_mul(factor))
+                        .unwrap_or(self.max_delay)
+                        .min(self.max_delay);
+                    backoff.saturating_add(backoff.mul_f64(fastrand::f64() / 2.0))
+                })
+                .min(self.max_delay);
+            logger::log_retry(attempt, &reason, delay, self.verbose);
+
+            sleep(delay).await;
+            req = retry_req;
+        }
+    }
 }
 
 impl HttpClient {
     fn with_defaults(builder: RequestBuilder) -> Self {
         Self {
             req: builder.timeout(Duration::from_secs(30)),
+            content_type: None,
+            follow_redirects: true,
+            max_redirects: 10,
+            retry: Retry {
+                count: 0,
+                delay: Duration::from_secs(1),
+                max_delay: Duration::from_secs(60),
+                on: vec![
+                    RetryOn::Status(429),
+                    RetryOn::Status(502),
+                    RetryOn::Status(503),
+                    RetryOn::Status(504),
+                    RetryOn::Connect,
+                ],
+                verbose: false,
+            },
         }
     }
 
-    pub fn get(url: &str) -> Self {
-        let builder = Client::new().get(url);
+    pub fn new(client: &Client, method: &Method, url: &str) -> Self {
+        let builder = client.request(method.into(), url);
 
         Self::with_defaults(builder)
     }
 
-    pub fn post(url: &str) -> Self {
-        let builder = Client::new().post(url);
+    pub async fn send(self) -> Result<(Request, Response, Vec<Redirect>, Attempt)> {
+        let (client, req) = self.req.build_split();
+        let mut req = req?;
 
-        Self::with_defaults(builder)
-    }
+        // The body content type is only a default, a Content-Type set by the
+        // user always wins
+        if let Some(content_type) = self.content_type {
+            if !req.headers().contains_key(CONTENT_TYPE) {
+                req.headers_mut()
+                    .insert(CONTENT_TYPE, HeaderValue::from_str(&content_type)?);
+            }
+        }
 
-    pub fn patch(url: &str) -> Self {
-        let builder = Client::new().patch(url);
+        let mut redirects = vec![];
+        loop {
+            let (cloned_req, replayable) = match req.try_clone() {
+                Some(cloned_req) => (cloned_req, true),
+                None => (Self::clone_without_body(&req), false),
+            };
+            let (res, attempt) = self.retry.execute(&client, req).await?;
+
+            let location = match res.status().as_u16() {
+                301 | 302 | 303 | 307 | 308 if self.follow_redirects => res.headers().get(LOCATION),
+                _ => None,
+            };
+            let Some(location) = location else {
+                return Ok((cloned_req, res, redirects, attempt));
+            };
+
+            if redirects.len() >= self.max_redirects {
+                return Err(anyhow!(
+                    "Stopped after {} redirects, the limit is {}",
+                    redirects.len(),
+                    self.max_redirects
+                ));
+            }
 
-        Self::with_defaults(builder)
+            let location = cloned_req
+                .url()
+                .join(location.to_str()?)
+                .context("Invalid redirect location")?;
+            let redirect = Redirect {
+                status: res.status(),
+                method: cloned_req.method().clone(),
+                url: cloned_req.url().clone(),
+                location,
+                next_method: cloned_req.method().clone(),
+                headers: res.headers().clone(),
+            };
+
+            req = Self::redirect_request(cloned_req, replayable, &redirect)?;
+            redirects.push(Redirect {
+                next_method: req.method().clone(),
+                ..redirect
+            });
+        }
     }
 
-    pub fn put(url: &str) -> Self {
-        let builder = Client::new().put(url);
+    // Follows the browser rules, 303 and a POST with 301 or 302 turn into a GET
+    // without a body, 307 and 308 repeat the request as is
+    fn redirect_request(
+        mut req: Request,
+        replayable: bool,
+        redirect: &Redirect,
+    ) -> Result<Request> {
+        let status = redirect.status.as_u16();
+        let to_get = match status {
+            303 => req.method() != reqwest::Method::HEAD,
+            301 | 302 => req.method() == reqwest::Method::POST,
+            _ => false,
+        };
 
-        Self::with_defaults(builder)
-    }
+        if to_get {
+            *req.method_mut() = reqwest::Method::GET;
+            *req.body_mut() = None;
+            for header in [CONTENT_TYPE, CONTENT_LENGTH, TRANSFER_ENCODING] {
+                req.headers_mut().remove(header);
+            }
+        } else if !replayable {
+            return Err(anyhow!(
+                "Unable to follow a {status} redirect, the request body can't be sent twice"
+            ));
+        }
 
-    pub fn delete(url: &str) -> Self {
-        let builder = Client::new().delete(url);
+        if req.url().origin() != redirect.location.origin() {
+            for header in [AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION] {
+                req.headers_mut().remove(header);
+            }
+        }
 
-        Self::with_defaults(builder)
+        *req.url_mut() = redirect.location.clone();
+
+        Ok(req)
     }
 
-    pub async fn send(self) -> Result<(Request, Response)> {
-        let req = self.req.build()?;
-        let cloned_req = req
-            .try_clone()
-            .ok_or(anyhow!("Failed to clone the request"))?;
-        let res = Client::new()
-            .execute(req)
-            .await
-            .context("Failed to execute request")?;
+    // Streamed bodies like multipart forms can't be cloned, the copy kept
+    // for logging only holds the request line and headers
+    fn clone_without_body(req: &Request) -> Request {
+        let mut cloned_req = Request::new(req.method().clone(), req.url().clone());
+        *cloned_req.headers_mut() = req.headers().clone();
+        *cloned_req.timeout_mut() = req.timeout().copied();
+        *cloned_req.version_mut() = req.version();
 
-        Ok((cloned_req, res))
+        cloned_req
     }
 
     pub fn with_timeout(mut self, duration: Duration) -> Self {
@@ -99,6 +769,56 @@ impl HttpClient {
         self
     }
 
+    pub fn with_follow_redirects(mut self, follow: bool) -> Self {
+        self.follow_redirects = follow;
+
+        self
+    }
+
+    pub fn with_max_redirects(mut self, max: usize) -> Self {
+        self.max_redirects = max;
+
+        self
+    }
+
+    pub fn with_retries(mut self, retries: u32) -> Self {
+        self.retry.count = retries;
+
+        self
+    }
+
+    pub fn with_retry_delay(mut self, seconds: f64) -> Result<Self> {
+        self.retry.delay = Duration::try_from_secs_f64(seconds)
+            .context(format!("Invalid retry delay {seconds}"))?;
+
+        Ok(self)
+    }
+
+    pub fn with_retry_max_delay(mut self, seconds: f64) -> Result<Self> {
+        self.retry.max_delay = Duration::try_from_secs_f64(seconds)
+            .context(format!("Invalid retry max delay {seconds}"))?;
+
+        Ok(self)
+    }
+
+    pub fn with_retry_on(mut self, retry_on: Vec<RetryOn>) -> Self {
+        self.retry.on = retry_on;
+
+        self
+    }
+
+    pub fn with_verbose(mut self, verbose: bool) -> Self {
+        self.retry.verbose = verbose;
+
+        self
+    }
+
+    pub fn with_range_from(mut self, offset: u64) -> Self {
+        self.req = self.req.header(RANGE, format!("bytes={offset}-"));
+
+        self
+    }
+
     pub fn with_headers_from_hash(mut self, headers: HashMap<String, String>) -> Self {
         for (k, v) in headers {
             self.req = self.req.header(k, v);
@@ -108,15 +828,12 @@ impl HttpClient {
     }
 
     pub fn with_header_from_str(mut self, header: &str) -> Result<Self> {
-        let header: String = header.chars().filter(|c| !c.is_whitespace()).collect();
-        let header: Vec<_> = header.split(':').collect();
-
-        let (k, v) = match (header.first(), header.get(1)) {
-            (Some(k), Some(v)) => (k.to_string(), v.to_string()),
-            _ => return Err(anyhow!("Invalid header format, must be 'KEY: VALUE'")),
-        };
+        let (name, value) = parse_header(header)?;
+        let name = HeaderName::from_str(name).context(format!("Invalid header name {name}"))?;
+        let value =
+            HeaderValue::from_str(value).context(format!("Invalid value for header {name}"))?;
 
-        self.req = self.req.header(k, v);
+        self.req = self.req.header(name, value);
 
         Ok(self)
     }
@@ -128,19 +845,18 @@ impl HttpClient {
     }
 
     pub fn with_basic_auth(mut self, credential: &str) -> Result<Self> {
-        let credential: String = credential.chars().filter(|c| !c.is_whitespace()).collect();
-        let credential: Vec<_> = credential.split(':').collect();
-
-        let (user, pass) = match (credential.first(), credential.get(1)) {
-            (Some(user), Some(pass)) => (user.to_string(), Some(pass.to_string())),
-            (Some(user), None) => (user.to_string(), None),
-            _ => {
-                return Err(anyhow!(
-                    "Invalid basic auth credentials, format must be 'user:password'"
-                ))
-            }
+        // A user name can't contain a colon, the password can
+        let (user, pass) = match credential.split_once(':') {
+            Some((user, pass)) => (user, Some(pass)),
+            None => (credential, None),
         };
 
+        if user.trim().is_empty() {
+            return Err(anyhow!(
+                "Invalid basic auth credentials, format must be 'user:password'"
+            ));
+        }
+
         self.req = self.req.basic_auth(user, pass);
 
         Ok(self)
@@ -148,20 +864,71 @@ impl HttpClient {
 
     pub fn with_json_body(mut self, body: String) -> Self {
         self.req = self.req.body(body);
-        self.req = self.req.header("Content-Type", "application/json");
+        self.content_type = Some("application/json".into());
 
         self
     }
 
     pub fn with_body(mut self, body: String) -> Self {
         self.req = self.req.body(body);
-        self.req = self
-            .req
-            .header("Content-Type", "application/x-www-form-urlencoded");
+        self.content_type = Some("application/x-www-form-urlencoded".into());
 
         self
     }
 
+    pub fn with_raw_body(mut self, body: Vec<u8>, content_type: Option<&str>) -> Self {
+        self.req = self.req.body(body);
+        self.content_type = content_type.map(|c| c.to_string());
+
+        self
+    }
+
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+
+        self
+    }
+
+    pub fn with_multipart(mut self, parts: &[FormPart]) -> Result<Self> {
+        let mut form = Form::new();
+
+        for part in parts {
+            let form_part = match (&part.value, &part.file) {
+                (Some(value), None) => Part::text(value.to_string()),
+                (None, Some(path)) => {
+                    let bytes = fs::read(path)
+                        .context(format!("Failed to read form file {}", path.display()))?;
+                    let file_name = path
+                        .file_name()
+                        .map(|n| n.to_string_lossy().to_string())
+                        .unwrap_or_default();
+                    let content_type = mime_guess::from_path(path).first_or_octet_stream();
+
+                    Part::bytes(bytes)
+                        .file_name(file_name)
+                        .mime_str(content_type.essence_str())?
+                }
+                _ => {
+                    return Err(anyhow!(
+                        "Form field {} must have either a value or a file",
+                        part.name
+                    ))
+                }
+            };
+
+            let form_part = match &part.content_type {
+                Some(content_type) => form_part.mime_str(content_type)?,
+                None => form_part,
+            };
+
+            form = form.part(part.name.to_string(), form_part);
+        }
+
+        self.req = self.req.multipart(form);
+
+        Ok(self)
+    }
+
     pub fn with_body_from_value(self, body: Option<Value>) -> Result<Self> {
         if body.is_none() {
             return Ok(self);
@@ -169,7 +936,9 @@ impl HttpClient {
 
         match body {
             Some(b) => match b {
-                Value::Object(_) => Ok(self.with_json_body(serde_json::to_string(&b)?)),
+                Value::Object(_) | Value::Array(_) => {
+                    Ok(self.with_json_body(serde_json::to_string(&b)?))
+                }
                 Value::String(s) => Ok(self.with_body(s)),
                 Value::Null => Ok(self),
                 _ => Err(anyhow!("Invalid request body")),
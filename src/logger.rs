@@ -1,20 +1,42 @@
-use std::collections::HashMap;
+use std::{
+    io::{self, Write},
+    time::Duration,
+};
 
-use anyhow::Result;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
 use colored_json::ToColoredJson;
 use reqwest::{
     header::{HeaderMap, HeaderValue},
     Request, StatusCode,
 };
+use serde_json::{json, Map, Value};
 
-fn log_headers(headers: &HeaderMap<HeaderValue>) -> Result<()> {
-    let mut header_map = HashMap::<&str, &str>::new();
+use crate::{
+    http::{HttpResponse, Redirect},
+    template::{expect::ExpectationFailure, path::Path},
+};
 
-    for (k, v) in headers {
-        header_map.insert(k.as_str(), v.to_str()?);
-    }
+#[derive(Clone, Copy, Default, PartialEq, ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Pretty,
+    Raw,
+    Json,
+    Headers,
+    Status,
+}
 
-    let json = serde_json::to_string(&header_map)?;
+#[derive(Clone, Default)]
+pub struct LogOptions {
+    pub verbose: bool,
+    pub format: OutputFormat,
+    pub filter: Option<Path>,
+    pub timing: bool,
+}
+
+fn log_headers(headers: &HeaderMap<HeaderValue>) -> Result<()> {
+    let json = headers_to_json(headers).to_string();
 
     println!("{}\n", json.to_colored_json_auto()?);
 
@@ -31,23 +53,201 @@ pub fn log_request(req: &Request, verbose: bool) -> Result<()> {
     log_headers(req.headers())
 }
 
-pub async fn log_response(
-    status: &StatusCode,
-    headers: &HeaderMap,
-    body: &str,
-    verbose: bool,
-) -> Result<()> {
+pub fn log_redirects(redirects: &[Redirect], verbose: bool) {
+    if !verbose {
+        return;
+    }
+
+    for redirect in redirects {
+        println!(
+            "Redirect: {} {} {} -> {}",
+            redirect.status, redirect.method, redirect.url, redirect.location
+        );
+
+        if redirect.method != redirect.next_method {
+            println!(
+                "Redirect method changed from {} to {}",
+                redirect.method, redirect.next_method
+            );
+        }
+    }
+}
+
+pub fn log_retry(attempt: u32, reason: &str, delay: Duration, verbose: bool) {
+    if !verbose {
+        return;
+    }
+
+    println!(
+        "Attempt {attempt} failed with {reason}, retrying in {}",
+        format_millis(delay)
+    );
+}
+
+pub fn log_retries_disabled(verbose: bool) {
     if verbose {
-        println!("Response Status: {:?}", status);
-        println!("Response Headers:");
-        log_headers(headers)?;
+        println!("Retries are disabled, the request body can't be sent twice");
     }
+}
 
-    println!("Response Body:");
-    match body.to_colored_json_auto() {
-        Ok(j) => println!("{j}"),
-        Err(_) => println!("{body}"),
+pub fn log_response_head(status: &StatusCode, headers: &HeaderMap) -> Result<()> {
+    println!("Response Status: {:?}", status);
+    println!("Response Headers:");
+    log_headers(headers)
+}
+
+pub fn log_response(
+    req: &Request,
+    response: &HttpResponse,
+    body: &[u8],
+    options: &LogOptions,
+) -> Result<()> {
+    let filtered = match &options.filter {
+        Some(path)
+            if matches!(
+                options.format,
+                OutputFormat::Pretty | OutputFormat::Raw | OutputFormat::Json
+            ) =>
+        {
+            let json: Value = serde_json::from_slice(body)
+                .context("Unable to filter the response, the body is not valid json")?;
+            Some(path.select(&json))
+        }
+        _ => None,
+    };
+
+    match options.format {
+        OutputFormat::Pretty => {
+            if options.verbose {
+                log_response_head(&response.status, &response.headers)?;
+                println!("Response Time: {}\n", format_millis(response.elapsed));
+            }
+
+            let body = filtered.map_or(response.body.clone(), |v| v.to_string());
+
+            println!("Response Body:");
+            match body.to_colored_json_auto() {
+                Ok(j) => println!("{j}"),
+                Err(_) => println!("{body}"),
+            }
+
+            if options.timing {
+                println!("{}", timing_summary(response));
+            }
+        }
+        OutputFormat::Raw => match filtered {
+            Some(Value::String(text)) => println!("{text}"),
+            Some(value) => println!("{value}"),
+            None => {
+                let mut stdout = io::stdout().lock();
+                stdout.write_all(body)?;
+                stdout.flush()?;
+            }
+        },
+        OutputFormat::Json => {
+            let body = filtered.unwrap_or_else(|| {
+                serde_json::from_slice::<Value>(body)
+                    .unwrap_or_else(|_| Value::String(response.body.clone()))
+            });
+            let mut envelope = json!({
+                "status": response.status.as_u16(),
+                "headers": headers_to_json(&response.headers),
+                "body": body,
+                "elapsed_ms": response.elapsed.as_millis() as u64,
+                "redirects": response.redirects.iter().map(|redirect| json!({
+                    "status": redirect.status.as_u16(),
+                    "method": redirect.method.as_str(),
+                    "url": redirect.url.as_str(),
+                    "location": redirect.location.as_str(),
+                })).collect::<Vec<_>>(),
+                "request": {
+                    "method": req.method().as_str(),
+                    "url": req.url().as_str(),
+                    "headers": headers_to_json(req.headers()),
+                },
+            });
+
+            if options.timing {
+                envelope["timing"] = timing_to_json(response);
+            }
+
+            println!("{}", envelope.to_string().to_colored_json_auto()?);
+        }
+        OutputFormat::Headers => {
+            println!("{:?} {}", response.version, response.status);
+            for (k, v) in &response.headers {
+                println!("{}: {}", k, String::from_utf8_lossy(v.as_bytes()));
+            }
+        }
+        OutputFormat::Status => println!("{}", response.status.as_u16()),
+    }
+
+    // Raw, headers and status output stay clean on stdout for piping
+    if options.timing
+        && matches!(
+            options.format,
+            OutputFormat::Raw | OutputFormat::Headers | OutputFormat::Status
+        )
+    {
+        eprintln!("{}", timing_summary(response));
     }
 
     Ok(())
 }
+
+fn format_millis(duration: Duration) -> String {
+    format!("{:.1}ms", duration.as_secs_f64() * 1000.0)
+}
+
+// Redirects and retries before the final attempt are reported on their own, the
+// first byte is counted from the start of the final attempt
+fn timing_summary(response: &HttpResponse) -> String {
+    let phase = |duration: Option<Duration>| duration.map_or("-".into(), format_millis);
+    let connection = &response.connection;
+
+    format!(
+        "Timing: redirects and retries {}, dns {}, connect {}, tls {}, first byte {}, total {}",
+        format_millis(response.blocked),
+        phase(connection.dns),
+        phase(connection.connect),
+        phase(connection.tls),
+        format_millis(response.waiting),
+        format_millis(response.elapsed),
+    )
+}
+
+fn timing_to_json(response: &HttpResponse) -> Value {
+    let millis = |duration: Duration| duration.as_secs_f64() * 1000.0;
+    let connection = &response.connection;
+
+    json!({
+        "redirects_and_retries_ms": millis(response.blocked),
+        "dns_ms": connection.dns.map(millis),
+        "connect_ms": connection.connect.map(millis),
+        "tls_ms": connection.tls.map(millis),
+        "first_byte_ms": millis(response.waiting),
+        "total_ms": millis(response.elapsed),
+    })
+}
+
+fn headers_to_json(headers: &HeaderMap) -> Value {
+    let mut map = Map::new();
+
+    for (k, v) in headers {
+        let value = String::from_utf8_lossy(v.as_bytes());
+        match map.get_mut(k.as_str()) {
+            Some(Value::String(existing)) => *existing = format!("{existing}, {value}"),
+            _ => {
+                map.insert(k.as_str().into(), Value::String(value.into()));
+            }
+        }
+    }
+
+    Value::Object(map)
+}
+
+pub fn log_expectation_failures(failures: &[ExpectationFailure]) {
+    for failure in failures {
+        eprintln!("{failure}");
+    }
+}
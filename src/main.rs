@@ -2,9 +2,11 @@ use clap::Parser;
 use cmd::{Cli, CommandHandler, Commands};
 
 mod cmd;
+mod har;
 mod http;
 mod logger;
 mod template;
+mod timing;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
@@ -16,8 +18,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         Commands::Put(handler) => handler.handle().await?,
         Commands::Patch(handler) => handler.handle().await?,
         Commands::Delete(handler) => handler.handle().await?,
+        Commands::Head(handler) => handler.handle().await?,
+        Commands::Options(handler) => handler.handle().await?,
+        Commands::Request(handler) => handler.handle().await?,
         Commands::Template(handler) => handler.handle().await?,
         Commands::Run(handler) => handler.handle().await?,
+        Commands::Flow(handler) => handler.handle().await?,
+        Commands::Cookies(handler) => handler.handle().await?,
     };
 
     Ok(())
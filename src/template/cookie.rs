@@ -0,0 +1,128 @@
+use std::{
+    convert::Infallible,
+    fs::{self, OpenOptions},
+    path::PathBuf,
+    sync::{Mutex, MutexGuard},
+};
+
+use anyhow::{anyhow, Context, Result};
+use chrono::{TimeZone, Utc};
+use cookie_store::{Cookie, CookieExpiration, CookieStore};
+use reqwest::{cookie, header::HeaderValue};
+use url::Url;
+
+use super::project::Project;
+
+pub struct CookieJar {
+    pub path: PathBuf,
+    store: Mutex<CookieStore>,
+}
+
+impl CookieJar {
+    pub fn open(project: &Project, variable_name: &str) -> Result<Self> {
+        let path = project.cookies_path().join(format!("{variable_name}.json"));
+
+        let store = match fs::read_to_string(&path) {
+            Ok(json) => {
+                let cookies: Vec<Cookie<'static>> = serde_json::from_str(&json)
+                    .context(format!("Failed to parse cookie jar {}", path.display()))?;
+
+                CookieStore::from_cookies(cookies.into_iter().map(Ok::<_, Infallible>), false)
+                    .unwrap_or_default()
+            }
+            Err(_) => CookieStore::default(),
+        };
+
+        Ok(Self {
+            path,
+            store: Mutex::new(store),
+        })
+    }
+
+    pub fn list(&self) -> Result<Vec<String>> {
+        Ok(self
+            .lock()?
+            .iter_unexpired()
+            .map(|cookie| {
+                let expires = match &cookie.expires {
+                    CookieExpiration::AtUtc(at) => Utc
+                        .timestamp_opt(at.unix_timestamp(), 0)
+                        .single()
+                        .map_or("unknown".into(), |at| at.to_rfc3339()),
+                    CookieExpiration::SessionEnd => "session".into(),
+                };
+
+                format!(
+                    "{}={} (domain: {}, path: {}, expires: {expires})",
+                    cookie.name(),
+                    cookie.value(),
+                    String::from(&cookie.domain),
+                    String::from(&cookie.path),
+                )
+            })
+            .collect())
+    }
+
+    // Session cookies are kept too, every run of the project is treated as the same session
+    pub fn save(&self) -> Result<()> {
+        let store = self.lock()?;
+        let cookies: Vec<_> = store.iter_unexpired().collect();
+
+        if let Some(dir) = self.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+
+        let file = OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(&self.path)?;
+
+        serde_json::to_writer(&file, &cookies).context("Failed to save cookie jar")
+    }
+
+    pub fn clear(self) -> Result<()> {
+        if !self.path.exists() {
+            return Ok(());
+        }
+
+        fs::remove_file(&self.path).context(format!(
+            "Failed to clear cookie jar {}",
+            self.path.display()
+        ))
+    }
+
+    fn lock(&self) -> Result<MutexGuard<'_, CookieStore>> {
+        self.store
+            .lock()
+            .map_err(|_| anyhow!("Failed to access cookie jar"))
+    }
+}
+
+impl cookie::CookieStore for CookieJar {
+    fn set_cookies(&self, cookie_headers: &mut dyn Iterator<Item = &HeaderValue>, url: &Url) {
+        let Ok(mut store) = self.lock() else {
+            return;
+        };
+
+        // Invalid cookies are dropped, the same way browsers do
+        for header in cookie_headers.filter_map(|h| h.to_str().ok()) {
+            let _ = store.parse(header, url);
+        }
+    }
+
+    fn cookies(&self, url: &Url) -> Option<HeaderValue> {
+        let cookies = self
+            .lock()
+            .ok()?
+            .get_request_values(url)
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<_>>()
+            .join("; ");
+
+        match cookies.is_empty() {
+            true => None,
+            false => HeaderValue::from_str(&cookies).ok(),
+        }
+    }
+}
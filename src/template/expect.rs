@@ -0,0 +1,291 @@
+use std::{collections::HashMap, fmt::Display};
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::http::HttpResponse;
+
+use super::path::Path;
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct Expectation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<StatusExpectation>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, HeaderExpectation>,
+
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub json: HashMap<String, JsonExpectation>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_latency_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StatusExpectation {
+    Code(u16),
+    Pattern(String),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum HeaderExpectation {
+    Present(bool),
+    Equals(String),
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct JsonExpectation {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub equals: Option<Value>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exists: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matches: Option<String>,
+}
+
+pub struct ExpectationFailure {
+    pub check: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl Display for ExpectationFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Expectation failed: {}", self.check)?;
+        writeln!(f, "  expected: {}", self.expected)?;
+        write!(f, "  actual:   {}", self.actual)
+    }
+}
+
+impl ExpectationFailure {
+    fn new(check: String, expected: impl Display, actual: impl Display) -> Self {
+        Self {
+            check,
+            expected: expected.to_string(),
+            actual: actual.to_string(),
+        }
+    }
+}
+
+impl StatusExpectation {
+    fn matches(&self, status: u16) -> Option<bool> {
+        let pattern = match self {
+            StatusExpectation::Code(code) => return Some(*code == status),
+            StatusExpectation::Pattern(p) => p.trim().to_lowercase(),
+        };
+
+        if let Some((start, end)) = pattern.split_once('-') {
+            let start: u16 = start.trim().parse().ok()?;
+            let end: u16 = end.trim().parse().ok()?;
+
+            return Some((start..=end).contains(&status));
+        }
+
+        if let Some(class) = pattern.strip_suffix("xx") {
+            let class: u16 = class.parse().ok()?;
+
+            return Some(status / 100 == class);
+        }
+
+        pattern.parse::<u16>().ok().map(|code| code == status)
+    }
+}
+
+impl Display for StatusExpectation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StatusExpectation::Code(code) => write!(f, "{code}"),
+            StatusExpectation::Pattern(pattern) => write!(f, "{pattern}"),
+        }
+    }
+}
+
+impl Expectation {
+    pub fn verify(&self, response: &HttpResponse) -> Vec<ExpectationFailure> {
+        let mut failures = vec![];
+
+        if let Some(status) = &self.status {
+            let actual = response.status.as_u16();
+            match status.matches(actual) {
+                Some(true) => {}
+                Some(false) => {
+                    failures.push(ExpectationFailure::new("status".into(), status, actual))
+                }
+                None => failures.push(ExpectationFailure::new(
+                    "status".into(),
+                    format!("{status} (invalid status expectation)"),
+                    actual,
+                )),
+            }
+        }
+
+        for (name, expected) in &self.headers {
+            let check = format!("header {name}");
+            let actual = response
+                .headers
+                .get(name.as_str())
+                .map(|v| String::from_utf8_lossy(v.as_bytes()).to_string());
+
+            match (expected, actual) {
+                (HeaderExpectation::Present(true), None) => {
+                    failures.push(ExpectationFailure::new(check, "present", "missing"))
+                }
+                (HeaderExpectation::Present(false), Some(actual)) => failures.push(
+                    ExpectationFailure::new(check, "missing", format!("{actual:?}")),
+                ),
+                (HeaderExpectation::Equals(expected), actual)
+                    if actual.as_ref() != Some(expected) =>
+                {
+                    failures.push(ExpectationFailure::new(
+                        check,
+                        format!("{expected:?}"),
+                        actual.map_or("missing".into(), |a| format!("{a:?}")),
+                    ))
+                }
+                _ => {}
+            }
+        }
+
+        if !self.json.is_empty() {
+            match serde_json::from_str::<Value>(&response.body) {
+                Ok(body) => {
+                    for (json_path, expected) in &self.json {
+                        match json_path.parse::<Path>() {
+                            Ok(path) => failures.extend(expected.verify(json_path, &path, &body)),
+                            Err(e) => failures.push(ExpectationFailure::new(
+                                format!("json {json_path}"),
+                                "valid path",
+                                e.to_string(),
+                            )),
+                        }
+                    }
+                }
+                Err(_) => failures.push(ExpectationFailure::new(
+                    "json".into(),
+                    "json response body",
+                    "response body is not valid json",
+                )),
+            }
+        }
+
+        if let Some(max_latency) = self.max_latency_ms {
+            let elapsed = response.elapsed.as_millis();
+            if elapsed > max_latency.into() {
+                failures.push(ExpectationFailure::new(
+                    "latency".into(),
+                    format!("at most {max_latency}ms"),
+                    format!("{elapsed}ms"),
+                ));
+            }
+        }
+
+        failures
+    }
+}
+
+impl JsonExpectation {
+    // A path exists when it matches anything, an explicit null included
+    fn verify(&self, json_path: &str, path: &Path, body: &Value) -> Vec<ExpectationFailure> {
+        let mut failures = vec![];
+        let check = format!("json {json_path}");
+        let actual = &path.select(body);
+
+        if let Some(exists) = self.exists {
+            if exists == path.query(body).is_empty() {
+                failures.push(ExpectationFailure::new(
+                    check.clone(),
+                    if exists { "present" } else { "missing" },
+                    if exists {
+                        "missing".into()
+                    } else {
+                        actual.to_string()
+                    },
+                ));
+            }
+        }
+
+        if let Some(expected) = &self.equals {
+            if expected != actual {
+                failures.push(ExpectationFailure::new(check.clone(), expected, actual));
+            }
+        }
+
+        if let Some(pattern) = &self.matches {
+            let text = match actual {
+                Value::String(s) => s.to_string(),
+                Value::Null => String::new(),
+                v => v.to_string(),
+            };
+
+            match Regex::new(pattern) {
+                Ok(regex) if regex.is_match(&text) => {}
+                Ok(_) => failures.push(ExpectationFailure::new(
+                    check,
+                    format!("match /{pattern}/"),
+                    actual,
+                )),
+                Err(_) => failures.push(ExpectationFailure::new(
+                    check,
+                    format!("match /{pattern}/ (invalid regex)"),
+                    actual,
+                )),
+            }
+        }
+
+        failures
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn exists(path: &str, exists: bool, body: &Value) -> bool {
+        let expectation = JsonExpectation {
+            exists: Some(exists),
+            ..Default::default()
+        };
+
+        expectation
+            .verify(path, &path.parse().unwrap(), body)
+            .is_empty()
+    }
+
+    #[test]
+    fn definite_path_exists() {
+        let body = json!({ "id": 1 });
+
+        assert!(exists("$.id", true, &body));
+        assert!(!exists("$.id", false, &body));
+        assert!(!exists("$.name", true, &body));
+        assert!(exists("$.name", false, &body));
+    }
+
+    #[test]
+    fn wildcard_path_exists_when_anything_matches() {
+        let body = json!({ "items": [{ "id": 1 }, { "id": 2 }] });
+
+        assert!(exists("$.items[*].id", true, &body));
+        assert!(exists("$.items[?(@.id > 1)]", true, &body));
+        assert!(!exists("$.items[?(@.id > 2)]", true, &body));
+        assert!(exists("$.items[?(@.id > 2)]", false, &body));
+        assert!(!exists("$.items[*].name", true, &body));
+    }
+
+    #[test]
+    fn explicit_null_exists() {
+        let body = json!({ "id": null, "items": [{ "id": null }] });
+
+        assert!(exists("$.id", true, &body));
+        assert!(!exists("$.id", false, &body));
+        assert!(exists("$.items[*].id", true, &body));
+    }
+}
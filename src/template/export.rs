@@ -0,0 +1,206 @@
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde_json::Value;
+
+use crate::http::{FormPart, Method};
+
+use super::TemplateRequest;
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ExportFormat {
+    Curl,
+    Httpie,
+    Wget,
+}
+
+enum ExportBody<'a> {
+    Json(String),
+    Form(String),
+    Raw(String),
+    RawFile(String),
+    Multipart(&'a [FormPart]),
+}
+
+pub fn render(request: &TemplateRequest, format: ExportFormat) -> Result<String> {
+    let body = match (&request.body, &request.raw, &request.multipart) {
+        (Some(Value::Object(_) | Value::Array(_)), None, None) => {
+            Some(ExportBody::Json(serde_json::to_string(&request.body)?))
+        }
+        (Some(Value::String(s)), None, None) => Some(ExportBody::Form(s.to_string())),
+        (None, Some(raw), None) => match (&raw.data, &raw.file) {
+            (Some(data), None) => Some(ExportBody::Raw(data.to_string())),
+            (None, Some(file)) => Some(ExportBody::RawFile(file.display().to_string())),
+            _ => return Err(anyhow!("Raw body must have either data or a file")),
+        },
+        (None, None, Some(parts)) => Some(ExportBody::Multipart(parts)),
+        (Some(Value::Null) | None, None, None) => None,
+        _ => return Err(anyhow!("Invalid request body")),
+    };
+
+    let mut headers: Vec<(String, String)> = request
+        .headers
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+    headers.sort();
+
+    let has_content_type = headers
+        .iter()
+        .any(|(k, _)| k.eq_ignore_ascii_case("Content-Type"));
+
+    let content_type = match &body {
+        Some(ExportBody::Json(_)) => Some("application/json"),
+        Some(ExportBody::Form(_)) => Some("application/x-www-form-urlencoded"),
+        Some(ExportBody::Raw(_) | ExportBody::RawFile(_)) => request
+            .raw
+            .as_ref()
+            .and_then(|raw| raw.content_type.as_deref()),
+        _ => None,
+    };
+
+    if let (Some(content_type), false) = (content_type, has_content_type) {
+        headers.push(("Content-Type".into(), content_type.into()));
+    }
+
+    let args = match format {
+        ExportFormat::Curl => curl_args(request, &headers, &body),
+        ExportFormat::Httpie => httpie_args(request, &headers, &body),
+        ExportFormat::Wget => wget_args(request, &headers, &body)?,
+    };
+
+    Ok(args.join(" \\\n  "))
+}
+
+fn curl_args(
+    request: &TemplateRequest,
+    headers: &[(String, String)],
+    body: &Option<ExportBody>,
+) -> Vec<String> {
+    let mut args = vec!["curl".to_string()];
+
+    match request.method {
+        Method::Get => {}
+        Method::Head => args.push("--head".into()),
+        _ => args.push(format!("-X {}", quote(&request.method.to_string()))),
+    }
+
+    for (k, v) in headers {
+        args.push(format!("-H {}", quote(&format!("{k}: {v}"))));
+    }
+
+    match body {
+        Some(ExportBody::Json(body)) | Some(ExportBody::Form(body)) => {
+            args.push(format!("--data-raw {}", quote(body)))
+        }
+        Some(ExportBody::Raw(body)) => args.push(format!("--data-binary {}", quote(body))),
+        Some(ExportBody::RawFile(file)) => {
+            args.push(format!("--data-binary {}", quote(&format!("@{file}"))))
+        }
+        Some(ExportBody::Multipart(parts)) => {
+            for part in parts.iter() {
+                args.push(match form_file(part) {
+                    Some(file) => format!("-F {}", quote(&format!("{}=@{file}", part.name))),
+                    None => format!("--form-string {}", quote(&form_value(part))),
+                });
+            }
+        }
+        None => {}
+    }
+
+    args.push(quote(&request.url));
+
+    args
+}
+
+fn httpie_args(
+    request: &TemplateRequest,
+    headers: &[(String, String)],
+    body: &Option<ExportBody>,
+) -> Vec<String> {
+    let mut args = vec!["http".to_string()];
+
+    match body {
+        Some(ExportBody::Json(body))
+        | Some(ExportBody::Form(body))
+        | Some(ExportBody::Raw(body)) => args.push(format!("--raw {}", quote(body))),
+        Some(ExportBody::Multipart(_)) => args.push("--multipart".into()),
+        Some(ExportBody::RawFile(_)) | None => {}
+    }
+
+    args.push(format!(
+        "{} {}",
+        quote(&request.method.to_string()),
+        quote(&request.url)
+    ));
+
+    for (k, v) in headers {
+        args.push(quote(&format!("{k}:{v}")));
+    }
+
+    if let Some(ExportBody::Multipart(parts)) = body {
+        for part in parts.iter() {
+            args.push(match form_file(part) {
+                Some(file) => quote(&format!("{}@{file}", part.name)),
+                None => quote(&form_value(part)),
+            });
+        }
+    }
+
+    if let Some(ExportBody::RawFile(file)) = body {
+        args.push(format!("< {}", quote(file)));
+    }
+
+    args
+}
+
+fn wget_args(
+    request: &TemplateRequest,
+    headers: &[(String, String)],
+    body: &Option<ExportBody>,
+) -> Result<Vec<String>> {
+    let mut args = vec!["wget".to_string(), "-O -".to_string()];
+
+    if request.method != Method::Get {
+        args.push(format!("--method={}", quote(&request.method.to_string())));
+    }
+
+    for (k, v) in headers {
+        args.push(format!("--header={}", quote(&format!("{k}: {v}"))));
+    }
+
+    match body {
+        Some(ExportBody::Json(body))
+        | Some(ExportBody::Form(body))
+        | Some(ExportBody::Raw(body)) => args.push(format!("--body-data={}", quote(body))),
+        Some(ExportBody::RawFile(file)) => args.push(format!("--body-file={}", quote(file))),
+        Some(ExportBody::Multipart(_)) => {
+            return Err(anyhow!("Multipart bodies can't be exported to wget"))
+        }
+        None => {}
+    }
+
+    args.push(quote(&request.url));
+
+    Ok(args)
+}
+
+fn form_file(part: &FormPart) -> Option<String> {
+    let file = part.file.as_ref()?.display().to_string();
+
+    Some(match &part.content_type {
+        Some(content_type) => format!("{file};type={content_type}"),
+        None => file,
+    })
+}
+
+fn form_value(part: &FormPart) -> String {
+    format!(
+        "{}={}",
+        part.name,
+        part.value.as_deref().unwrap_or_default()
+    )
+}
+
+fn quote(s: &str) -> String {
+    shell_words::quote(s).to_string()
+}
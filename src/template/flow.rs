@@ -0,0 +1,126 @@
+use std::{
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context, Result};
+use dialoguer::Editor;
+use serde::{Deserialize, Serialize};
+
+use super::{project::Project, Template};
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct FlowDefinition {
+    pub templates: Vec<String>,
+}
+
+pub struct Flow {
+    pub name: String,
+    pub path: PathBuf,
+    pub definition: FlowDefinition,
+}
+
+impl Flow {
+    pub fn create(project: &Project, flow_name: &str) -> Result<Self> {
+        let mut flow = Self::new(project, flow_name);
+
+        flow.edit(project)?.save()?;
+
+        Ok(flow)
+    }
+
+    pub fn get(project: &Project, flow_name: &str) -> Result<Self> {
+        let mut flow = Self::new(project, flow_name);
+
+        let json = fs::read_to_string(&flow.path).context(format!(
+            "Flow {flow_name} not found in project {}",
+            project.name
+        ))?;
+        flow.definition = serde_json::from_str(&json)?;
+
+        Ok(flow)
+    }
+
+    pub fn list(project: &Project) -> Result<Vec<String>> {
+        let path = project.flows_path();
+        if !path.is_dir() {
+            return Ok(vec![]);
+        }
+
+        let mut flow_names = vec![];
+        for file in fs::read_dir(path)?.flatten() {
+            if !file.path().is_file() {
+                continue;
+            }
+
+            flow_names.push(
+                file.path()
+                    .file_stem()
+                    .and_then(|s| s.to_str())
+                    .map(|s| s.to_string())
+                    .ok_or(anyhow!("Failed to read flow name"))?,
+            )
+        }
+
+        Ok(flow_names)
+    }
+
+    pub fn save(&mut self) -> Result<&mut Self> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+
+        let json = serde_json::to_string(&self.definition)?;
+
+        file.write_all(json.as_bytes())
+            .context(format!("Failed to save flow {}", self.name))?;
+
+        Ok(self)
+    }
+
+    pub fn edit(&mut self, project: &Project) -> Result<&mut Self> {
+        let json = serde_json::to_string_pretty(&self.definition)?;
+
+        let flow_edit = Editor::new()
+            .extension(".json")
+            .edit(&json)?
+            .ok_or(anyhow!("Failed to edit flow"))?;
+
+        let definition: FlowDefinition = serde_json::from_str(&flow_edit)?;
+
+        let templates = Template::list(project)?;
+        if let Some(missing) = definition
+            .templates
+            .iter()
+            .find(|name| !templates.contains(name))
+        {
+            return Err(anyhow!(
+                "Template {missing} not found in project {}",
+                project.name
+            ));
+        }
+
+        self.definition = definition;
+
+        Ok(self)
+    }
+
+    pub fn delete(self) -> Result<()> {
+        fs::remove_file(self.path).context(format!("Failed to delete flow {}", self.name))
+    }
+
+    fn new(project: &Project, flow_name: &str) -> Self {
+        Self {
+            name: flow_name.to_string(),
+            path: project.flows_path().join(format!("{flow_name}.json")),
+            definition: FlowDefinition::default(),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::{collections::HashMap, fs};
+
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde_json::Value;
+use url::form_urlencoded;
+
+use crate::{
+    http::{parse_header, FormPart, Method},
+    template::TemplateRequest,
+};
+
+const IGNORED_OPTIONS_WITH_VALUE: [&str; 34] = [
+    "-o",
+    "--output",
+    "-m",
+    "--max-time",
+    "--connect-timeout",
+    "-w",
+    "--write-out",
+    "--retry",
+    "--retry-delay",
+    "--retry-max-time",
+    "-U",
+    "--proxy-user",
+    "--noproxy",
+    "--capath",
+    "--cert-type",
+    "--key-type",
+    "--pass",
+    "--interface",
+    "--limit-rate",
+    "--connect-to",
+    "-x",
+    "--proxy",
+    "--cacert",
+    "-E",
+    "--cert",
+    "--key",
+    "-c",
+    "--cookie-jar",
+    "--resolve",
+    "--max-redirs",
+    "-T",
+    "--upload-file",
+    "-r",
+    "--range",
+];
+
+#[derive(Default)]
+struct CurlCommand {
+    url: Option<String>,
+    method: Option<Method>,
+    headers: HashMap<String, String>,
+    data: Vec<String>,
+    form: Vec<FormPart>,
+    json: bool,
+    get: bool,
+    head: bool,
+}
+
+pub fn parse(command: &str) -> Result<TemplateRequest> {
+    let command = command.replace("\\\r\n", " ").replace("\\\n", " ");
+    let args = shell_words::split(&command).context("Failed to parse curl command")?;
+
+    let mut curl = CurlCommand::default();
+    let mut args = args.into_iter().peekable();
+
+    if args.peek().map(|a| a == "curl").unwrap_or(false) {
+        args.next();
+    }
+
+    while let Some(arg) = args.next() {
+        let (option, attached) = split_attached_value(&arg);
+        let mut value = || -> Result<String> {
+            match &attached {
+                Some(v) => Ok(v.to_string()),
+                None => args
+                    .next()
+                    .ok_or(anyhow!("Missing value for curl option {option}")),
+            }
+        };
+
+        match option.as_str() {
+            "-X" | "--request" => curl.method = Some(value()?.parse()?),
+            "-H" | "--header" => curl.add_header(&value()?)?,
+            "-d" | "--data" | "--data-ascii" | "--data-binary" => {
+                let data = value()?;
+                curl.data.push(read_data_value(&data)?);
+            }
+            "--data-raw" => curl.data.push(value()?),
+            "--data-urlencode" => curl.data.push(url_encode_data(&value()?)?),
+            "--json" => {
+                let data = value()?;
+                curl.data.push(read_data_value(&data)?);
+                curl.json = true;
+            }
+            "-F" | "--form" => curl.form.push(value()?.parse()?),
+            "--form-string" => {
+                let field = value()?;
+                let (name, value) = field
+                    .split_once('=')
+                    .ok_or(anyhow!("Invalid curl form field {field}"))?;
+
+                curl.form.push(FormPart {
+                    name: name.to_string(),
+                    value: Some(value.to_string()),
+                    file: None,
+                    content_type: None,
+                });
+            }
+            "-u" | "--user" => {
+                let credentials = STANDARD.encode(value()?);
+                if !curl.has_header("Authorization") {
+                    curl.headers
+                        .insert("Authorization".into(), format!("Basic {credentials}"));
+                }
+            }
+            "-A" | "--user-agent" => {
+                curl.headers.insert("User-Agent".into(), value()?);
+            }
+            "-e" | "--referer" => {
+                curl.headers.insert("Referer".into(), value()?);
+            }
+            "-b" | "--cookie" => curl.join_header("Cookie", &value()?),
+            "--url" => curl.set_url(value()?)?,
+            "-G" | "--get" => curl.get = true,
+            "-I" | "--head" => curl.head = true,
+            o if IGNORED_OPTIONS_WITH_VALUE.contains(&o) => {
+                value()?;
+            }
+            o if o.starts_with('-') => continue,
+            _ => curl.set_url(arg)?,
+        }
+    }
+
+    curl.into_request()
+}
+
+impl CurlCommand {
+    // A second url is usually the value of an option that isn't known here
+    fn set_url(&mut self, url: String) -> Result<()> {
+        if let Some(existing) = &self.url {
+            return Err(anyhow!(
+                "Unexpected curl argument {url} after the url {existing}, it may belong to an unsupported option"
+            ));
+        }
+
+        self.url = Some(url);
+
+        Ok(())
+    }
+
+    fn add_header(&mut self, header: &str) -> Result<()> {
+        let (name, value) = parse_header(header).context("Invalid curl header")?;
+        self.join_header(name, value);
+
+        Ok(())
+    }
+
+    // Templates keep one value per header, so repeated headers are joined, cookies
+    // with '; ' as the Cookie header needs
+    fn join_header(&mut self, name: &str, value: &str) {
+        let separator = match name.eq_ignore_ascii_case("Cookie") {
+            true => "; ",
+            false => ", ",
+        };
+
+        match self
+            .headers
+            .iter_mut()
+            .find(|(k, _)| k.eq_ignore_ascii_case(name))
+        {
+            Some((_, existing)) => *existing = format!("{existing}{separator}{value}"),
+            None => {
+                self.headers.insert(name.into(), value.into());
+            }
+        }
+    }
+
+    fn has_header(&self, name: &str) -> bool {
+        self.headers.keys().any(|k| k.eq_ignore_ascii_case(name))
+    }
+
+    fn into_request(mut self) -> Result<TemplateRequest> {
+        let mut url = self
+            .url
+            .take()
+            .ok_or(anyhow!("No url found in curl command"))?;
+
+        let data = if self.json {
+            self.data.join("")
+        } else {
+            self.data.join("&")
+        };
+
+        if self.json {
+            if !self.has_header("Content-Type") {
+                self.headers
+                    .insert("Content-Type".into(), "application/json".into());
+            }
+
+            if !self.has_header("Accept") {
+                self.headers
+                    .insert("Accept".into(), "application/json".into());
+            }
+        }
+
+        let mut body = None;
+        if self.get && !data.is_empty() {
+            let separator = if url.contains('?') { '&' } else { '?' };
+            url = format!("{url}{separator}{data}");
+        } else if !data.is_empty() {
+            body = match serde_json::from_str::<Value>(&data) {
+                Ok(json @ Value::Object(_)) => Some(json),
+                _ => Some(Value::String(data)),
+            };
+        }
+
+        let multipart = match self.form.is_empty() {
+            true => None,
+            false if body.is_some() => {
+                return Err(anyhow!("Curl command can't mix data and form fields"))
+            }
+            false => Some(self.form),
+        };
+
+        let has_body = body.is_some() || multipart.is_some();
+        let method = match (self.method, self.head, self.get, has_body) {
+            (Some(method), _, _, _) => method,
+            (None, true, _, _) => Method::Head,
+            (None, _, true, _) => Method::Get,
+            (None, _, _, true) => Method::Post,
+            _ => Method::Get,
+        };
+
+        Ok(TemplateRequest {
+            url,
+            method,
+            headers: self.headers,
+            body,
+            multipart,
+            ..Default::default()
+        })
+    }
+}
+
+fn split_attached_value(arg: &str) -> (String, Option<String>) {
+    let attachable = ["-X", "-H", "-d", "-F", "-u", "-A", "-e", "-b"];
+    match attachable
+        .iter()
+        .find(|o| arg.starts_with(*o) && arg.len() > 2)
+    {
+        Some(option) => (option.to_string(), Some(arg[option.len()..].to_string())),
+        None => (arg.to_string(), None),
+    }
+}
+
+fn read_data_value(data: &str) -> Result<String> {
+    match data.strip_prefix('@') {
+        Some(path) => fs::read_to_string(path).context(format!("Failed to read data file {path}")),
+        None => Ok(data.to_string()),
+    }
+}
+
+fn url_encode_data(data: &str) -> Result<String> {
+    let encode = |s: &str| form_urlencoded::byte_serialize(s.as_bytes()).collect::<String>();
+
+    if let Some(content) = data.strip_prefix('=') {
+        return Ok(encode(content));
+    }
+
+    if let Some((name, content)) = data.split_once('=') {
+        return Ok(format!("{name}={}", encode(content)));
+    }
+
+    if let Some((name, path)) = data.split_once('@') {
+        let content =
+            fs::read_to_string(path).context(format!("Failed to read data file {path}"))?;
+
+        return Ok(match name {
+            "" => encode(&content),
+            _ => format!("{name}={}", encode(&content)),
+        });
+    }
+
+    Ok(encode(data))
+}
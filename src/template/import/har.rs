@@ -0,0 +1,100 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+use url::Url;
+
+use crate::{
+    har::{Har, HarRequest},
+    template::{project::Project, RawBody, TemplateRequest},
+};
+
+use super::{create_default_variable, sanitize_name, save_templates, ImportReport};
+
+const SKIPPED_HEADERS: [&str; 4] = ["host", "content-length", "connection", "accept-encoding"];
+
+pub fn import(har_path: &Path, project_name: &str) -> Result<ImportReport> {
+    let json = fs::read_to_string(har_path).context("Failed to read har file")?;
+    let har: Har = serde_json::from_str(&json).context("Invalid har file")?;
+
+    let mut project = Project::create(project_name.to_string())?;
+    let mut report = ImportReport::new(&project.name);
+
+    create_default_variable(&mut project, HashMap::new())?;
+
+    let mut requests = vec![];
+    for entry in &har.log.entries {
+        let name = entry_name(&entry.request);
+
+        match convert_request(&entry.request) {
+            Ok(request) => requests.push((name, request)),
+            Err(e) => report.warn(&name, &e.to_string()),
+        }
+    }
+
+    save_templates(project, requests, &mut report)?;
+
+    Ok(report)
+}
+
+fn convert_request(request: &HarRequest) -> Result<TemplateRequest> {
+    let mut headers = HashMap::new();
+    for header in &request.headers {
+        let name = header.name.to_lowercase();
+        if name.starts_with(':') || SKIPPED_HEADERS.contains(&name.as_str()) {
+            continue;
+        }
+
+        headers.insert(header.name.to_string(), header.value.to_string());
+    }
+
+    let mut body = None;
+    let mut raw = None;
+
+    if let Some(post_data) = &request.post_data {
+        let text = match &post_data.text {
+            Some(text) => text.to_string(),
+            None => post_data
+                .params
+                .iter()
+                .map(|p| format!("{}={}", p.name, p.value))
+                .collect::<Vec<_>>()
+                .join("&"),
+        };
+
+        let is_form = post_data.mime_type.is_empty()
+            || post_data
+                .mime_type
+                .starts_with("application/x-www-form-urlencoded");
+
+        match serde_json::from_str::<Value>(&text) {
+            _ if text.is_empty() => {}
+            Ok(json @ (Value::Object(_) | Value::Array(_))) => body = Some(json),
+            _ if is_form => body = Some(Value::String(text)),
+            _ => {
+                raw = Some(RawBody {
+                    content_type: Some(post_data.mime_type.to_string()),
+                    data: Some(text),
+                    file: None,
+                })
+            }
+        }
+    }
+
+    Ok(TemplateRequest {
+        url: request.url.to_string(),
+        method: request.method.parse()?,
+        headers,
+        body,
+        raw,
+        ..Default::default()
+    })
+}
+
+fn entry_name(request: &HarRequest) -> String {
+    let path = Url::parse(&request.url)
+        .map(|url| url.path().to_string())
+        .unwrap_or_default();
+
+    sanitize_name(&format!("{}-{path}", request.method))
+}
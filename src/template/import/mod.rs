@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use serde_json::Value;
+
+use super::{project::Project, Template, TemplateRequest};
+
+pub mod curl;
+pub mod har;
+pub mod openapi;
+pub mod postman;
+
+pub struct ImportReport {
+    pub project: String,
+    pub templates: Vec<String>,
+    pub warnings: Vec<String>,
+}
+
+impl ImportReport {
+    fn new(project: &str) -> Self {
+        Self {
+            project: project.to_string(),
+            templates: vec![],
+            warnings: vec![],
+        }
+    }
+
+    fn warn(&mut self, source: &str, message: &str) {
+        self.warnings.push(format!("{source}: {message}"));
+    }
+}
+
+// A project that already has variable sets keeps them as they are
+fn create_default_variable(project: &mut Project, contents: HashMap<String, Value>) -> Result<()> {
+    if project.variables()?.is_empty() {
+        project.create_variable("default")?;
+        project.current_variable()?.contents = contents;
+        project.current_variable()?.save()?;
+    }
+
+    Ok(())
+}
+
+// Templates already in the project are never overwritten, a taken name gets a suffix
+fn save_templates(
+    mut project: Project,
+    requests: Vec<(String, TemplateRequest)>,
+    report: &mut ImportReport,
+) -> Result<Project> {
+    let mut taken = Template::list(&project)?;
+    for (name, request) in requests {
+        let name = unique_name(&taken, &name);
+        let mut template = Template::new(project, &name);
+        template.request = request;
+        template.save()?;
+
+        project = template.project;
+        taken.push(name.clone());
+        report.templates.push(name);
+    }
+
+    Ok(project)
+}
+
+fn sanitize_name(name: &str) -> String {
+    let name: String = name
+        .trim()
+        .to_lowercase()
+        .chars()
+        .map(|c| match c.is_alphanumeric() || c == '_' {
+            true => c,
+            false => '-',
+        })
+        .collect();
+
+    name.split('-')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+fn unique_name(taken: &[String], name: &str) -> String {
+    let name = match name {
+        "" => "request",
+        n => n,
+    };
+
+    let mut candidate = name.to_string();
+    let mut suffix = 2;
+    while taken.contains(&candidate) {
+        candidate = format!("{name}-{suffix}");
+        suffix += 1;
+    }
+
+    candidate
+}
+
+fn variable_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c.is_alphanumeric() || c == '_' || c == '-' {
+            true => c,
+            false => '_',
+        })
+        .collect()
+}
@@ -0,0 +1,330 @@
+use std::{collections::HashMap, fs, path::Path};
+
+use anyhow::{anyhow, Context, Result};
+use regex::{Captures, Regex};
+use serde_json::{json, Map, Value};
+
+use crate::template::{project::Project, TemplateRequest};
+
+use super::{create_default_variable, sanitize_name, save_templates, variable_name, ImportReport};
+
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+const MAX_SCHEMA_DEPTH: usize = 8;
+
+pub fn import(spec_path: &Path, project_name: &str) -> Result<ImportReport> {
+    let contents = fs::read_to_string(spec_path).context("Failed to read openapi spec")?;
+    let spec: Value = match serde_json::from_str(&contents) {
+        Ok(spec) => spec,
+        Err(_) => serde_yaml::from_str(&contents).context("Invalid openapi spec")?,
+    };
+
+    if !spec["openapi"]
+        .as_str()
+        .unwrap_or_default()
+        .starts_with('3')
+    {
+        return Err(anyhow!("Only openapi 3 specs are supported"));
+    }
+
+    let mut project = Project::create(project_name.to_string())?;
+    let mut report = ImportReport::new(&project.name);
+
+    let base_url = spec["servers"][0]["url"].as_str().unwrap_or_default();
+    create_default_variable(
+        &mut project,
+        HashMap::from([("base_url".into(), Value::String(base_url.into()))]),
+    )?;
+
+    let paths = spec["paths"]
+        .as_object()
+        .ok_or(anyhow!("Openapi spec has no paths"))?;
+
+    let mut requests = vec![];
+    for (path, path_item) in paths {
+        let path_item = resolve(&spec, path_item);
+
+        for method in METHODS {
+            let operation = &path_item[method];
+            if !operation.is_object() {
+                continue;
+            }
+
+            let name = operation["operationId"]
+                .as_str()
+                .map(sanitize_name)
+                .unwrap_or_else(|| sanitize_name(&format!("{method}-{path}")));
+
+            let request = convert_operation(
+                &spec,
+                path,
+                method,
+                path_item,
+                operation,
+                &name,
+                &mut report,
+            )?;
+
+            requests.push((name, request));
+        }
+    }
+
+    save_templates(project, requests, &mut report)?;
+
+    Ok(report)
+}
+
+fn convert_operation(
+    spec: &Value,
+    path: &str,
+    method: &str,
+    path_item: &Value,
+    operation: &Value,
+    name: &str,
+    report: &mut ImportReport,
+) -> Result<TemplateRequest> {
+    let path_regex = Regex::new(r"\{([^{}]+)\}").expect("Failed building openapi path regex");
+    let path = path_regex.replace_all(path, |captures: &Captures| {
+        format!("{{{{{}}}}}", variable_name(&captures[1]))
+    });
+
+    let mut url = format!("{{{{base_url}}}}{path}");
+    let mut query = vec![];
+    let mut headers = HashMap::new();
+
+    let parameters = path_item["parameters"]
+        .as_array()
+        .into_iter()
+        .chain(operation["parameters"].as_array())
+        .flatten()
+        .map(|p| resolve(spec, p));
+
+    for parameter in parameters {
+        let required = parameter["required"].as_bool().unwrap_or(false);
+        let param_name = parameter["name"].as_str().unwrap_or_default();
+        let variable = format!("{{{{{}}}}}", variable_name(param_name));
+
+        match (parameter["in"].as_str(), required) {
+            (Some("query"), true) => query.push(format!("{param_name}={variable}")),
+            (Some("header"), true) => {
+                headers.insert(param_name.to_string(), variable);
+            }
+            (Some("cookie"), true) => report.warn(
+                name,
+                &format!("cookie parameter {param_name} was not converted"),
+            ),
+            _ => {}
+        }
+    }
+
+    if !query.is_empty() {
+        url = format!("{url}?{}", query.join("&"));
+    }
+
+    let security = operation
+        .get("security")
+        .or(spec.get("security"))
+        .and_then(|s| s.as_array())
+        .and_then(|s| s.first())
+        .and_then(|s| s.as_object());
+
+    for scheme_name in security.into_iter().flat_map(|s| s.keys()) {
+        let scheme = resolve(spec, &spec["components"]["securitySchemes"][scheme_name]);
+
+        match (
+            scheme["type"].as_str(),
+            scheme["scheme"].as_str(),
+            scheme["in"].as_str(),
+        ) {
+            (Some("http"), Some(s), _) if s.eq_ignore_ascii_case("bearer") => {
+                headers.insert("Authorization".into(), "Bearer {{token}}".into());
+            }
+            (Some("http"), Some(s), _) if s.eq_ignore_ascii_case("basic") => {
+                headers.insert("Authorization".into(), "Basic {{basic_credentials}}".into());
+            }
+            (Some("apiKey"), _, Some("header")) => {
+                let header = scheme["name"].as_str().unwrap_or("X-API-Key");
+                headers.insert(
+                    header.into(),
+                    format!("{{{{{}}}}}", variable_name(scheme_name)),
+                );
+            }
+            _ => report.warn(
+                name,
+                &format!("security scheme {scheme_name} was not converted"),
+            ),
+        }
+    }
+
+    let body = match operation.get("requestBody") {
+        Some(request_body) => convert_body(
+            spec,
+            resolve(spec, request_body),
+            &mut headers,
+            name,
+            report,
+        ),
+        None => None,
+    };
+
+    Ok(TemplateRequest {
+        url,
+        method: method.parse()?,
+        headers,
+        body,
+        ..Default::default()
+    })
+}
+
+fn convert_body(
+    spec: &Value,
+    request_body: &Value,
+    headers: &mut HashMap<String, String>,
+    name: &str,
+    report: &mut ImportReport,
+) -> Option<Value> {
+    let content = request_body["content"].as_object()?;
+
+    let (content_type, media) = content
+        .iter()
+        .find(|(content_type, _)| content_type.contains("json"))
+        .or_else(|| {
+            content
+                .iter()
+                .find(|(content_type, _)| *content_type == "application/x-www-form-urlencoded")
+        })
+        .or_else(|| content.iter().next())?;
+
+    let example = media
+        .get("example")
+        .or_else(|| {
+            media["examples"]
+                .as_object()
+                .and_then(|examples| examples.values().next())
+                .map(|example| &resolve(spec, example)["value"])
+        })
+        .cloned()
+        .unwrap_or_else(|| example_from_schema(spec, &media["schema"], &mut vec![]));
+
+    if content_type.contains("json") {
+        return match example {
+            Value::Object(_) => Some(example),
+            Value::Null => None,
+            v => {
+                headers.insert("Content-Type".into(), content_type.to_string());
+
+                Some(Value::String(v.to_string()))
+            }
+        };
+    }
+
+    if content_type == "application/x-www-form-urlencoded" {
+        let data: Vec<_> = example
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(k, v)| match v {
+                Value::String(s) => format!("{k}={s}"),
+                v => format!("{k}={v}"),
+            })
+            .collect();
+
+        return Some(Value::String(data.join("&")));
+    }
+
+    report.warn(
+        name,
+        &format!("request body {content_type} was not converted"),
+    );
+
+    None
+}
+
+fn example_from_schema<'a>(spec: &'a Value, schema: &'a Value, refs: &mut Vec<&'a str>) -> Value {
+    if let Some(reference) = schema["$ref"].as_str() {
+        // Recursive schemas stop at the first repeated reference
+        if refs.contains(&reference) || refs.len() > MAX_SCHEMA_DEPTH {
+            return Value::Null;
+        }
+
+        refs.push(reference);
+        let example = example_from_schema(spec, resolve(spec, schema), refs);
+        refs.pop();
+
+        return example;
+    }
+
+    if let Some(example) = schema.get("example").or(schema.get("default")) {
+        return example.clone();
+    }
+
+    if let Some(value) = schema["enum"].as_array().and_then(|e| e.first()) {
+        return value.clone();
+    }
+
+    if let Some(all_of) = schema["allOf"].as_array() {
+        let mut merged = Map::new();
+        for schema in all_of {
+            if let Value::Object(o) = example_from_schema(spec, schema, refs) {
+                merged.extend(o);
+            }
+        }
+
+        return Value::Object(merged);
+    }
+
+    if let Some(schema) = schema["oneOf"]
+        .as_array()
+        .or(schema["anyOf"].as_array())
+        .and_then(|s| s.first())
+    {
+        return example_from_schema(spec, schema, refs);
+    }
+
+    match schema["type"].as_str() {
+        Some("object") | None if schema["properties"].is_object() => {
+            let properties = schema["properties"].as_object().into_iter().flatten();
+
+            Value::Object(
+                properties
+                    .map(|(k, v)| (k.to_string(), example_from_schema(spec, v, refs)))
+                    .filter(|(_, v)| !v.is_null())
+                    .collect(),
+            )
+        }
+        Some("object") => json!({}),
+        Some("array") => match example_from_schema(spec, &schema["items"], refs) {
+            Value::Null => json!([]),
+            item => json!([item]),
+        },
+        Some("string") => match schema["format"].as_str() {
+            Some("date-time") => json!("1970-01-01T00:00:00Z"),
+            Some("date") => json!("1970-01-01"),
+            Some("uuid") => json!("00000000-0000-0000-0000-000000000000"),
+            Some("email") => json!("user@example.com"),
+            Some("uri") | Some("url") => json!("https://example.com"),
+            _ => json!("string"),
+        },
+        Some("integer") | Some("number") => json!(0),
+        Some("boolean") => json!(false),
+        _ => Value::Null,
+    }
+}
+
+fn resolve<'a>(spec: &'a Value, value: &'a Value) -> &'a Value {
+    let mut value = value;
+
+    // References can point to other references, the depth limit avoids cycles
+    for _ in 0..MAX_SCHEMA_DEPTH {
+        let reference = match value["$ref"].as_str().and_then(|r| r.strip_prefix('#')) {
+            Some(reference) => reference,
+            None => return value,
+        };
+
+        value = spec.pointer(reference).unwrap_or(&Value::Null);
+    }
+
+    value
+}
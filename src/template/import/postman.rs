@@ -0,0 +1,514 @@
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use regex::{Captures, Regex};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::{
+    http::{FormPart, Method},
+    template::{project::Project, RawBody, TemplateRequest},
+};
+
+use super::{create_default_variable, save_templates, variable_name, ImportReport};
+
+#[derive(Deserialize)]
+struct Collection {
+    info: Info,
+
+    #[serde(default)]
+    item: Vec<Item>,
+
+    #[serde(default)]
+    variable: Vec<KeyValue>,
+
+    auth: Option<Auth>,
+
+    #[serde(default)]
+    event: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+struct Info {
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct Item {
+    name: String,
+
+    #[serde(default)]
+    item: Option<Vec<Item>>,
+
+    request: Option<Request>,
+
+    auth: Option<Auth>,
+
+    #[serde(default)]
+    event: Vec<Value>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Request {
+    Url(String),
+    Full(Box<FullRequest>),
+}
+
+#[derive(Deserialize)]
+struct FullRequest {
+    method: Option<String>,
+
+    url: Option<Url>,
+
+    #[serde(default)]
+    header: Vec<KeyValue>,
+
+    body: Option<Body>,
+
+    auth: Option<Auth>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Url {
+    Raw(String),
+    Parts { raw: String },
+}
+
+#[derive(Deserialize)]
+struct Body {
+    mode: Option<String>,
+    raw: Option<String>,
+    options: Option<Value>,
+
+    #[serde(default)]
+    urlencoded: Vec<KeyValue>,
+
+    #[serde(default)]
+    formdata: Vec<FormData>,
+
+    graphql: Option<Value>,
+}
+
+#[derive(Deserialize)]
+struct Auth {
+    #[serde(rename = "type")]
+    auth_type: String,
+
+    #[serde(flatten)]
+    params: HashMap<String, Value>,
+}
+
+#[derive(Deserialize)]
+struct KeyValue {
+    key: String,
+
+    #[serde(default)]
+    value: Value,
+
+    #[serde(default)]
+    disabled: bool,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FormData {
+    key: String,
+
+    #[serde(default)]
+    value: Value,
+
+    #[serde(default, rename = "type")]
+    form_type: Option<String>,
+
+    #[serde(default)]
+    src: Value,
+
+    content_type: Option<String>,
+
+    #[serde(default)]
+    disabled: bool,
+}
+
+#[derive(Deserialize)]
+struct Environment {
+    name: String,
+
+    #[serde(default)]
+    values: Vec<EnvironmentValue>,
+}
+
+#[derive(Deserialize)]
+struct EnvironmentValue {
+    key: String,
+
+    #[serde(default)]
+    value: Value,
+
+    #[serde(default = "enabled")]
+    enabled: bool,
+}
+
+fn enabled() -> bool {
+    true
+}
+
+pub fn import(
+    collection_path: &Path,
+    project_name: Option<&str>,
+    environment_paths: &[impl AsRef<Path>],
+) -> Result<ImportReport> {
+    let json = fs::read_to_string(collection_path).context("Failed to read postman collection")?;
+    let collection: Collection =
+        serde_json::from_str(&json).context("Invalid postman v2.1 collection")?;
+
+    let project_name = project_name
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| super::sanitize_name(&collection.info.name));
+    let mut project = Project::create(project_name)?;
+    let mut report = ImportReport::new(&project.name);
+
+    let collection_variables: HashMap<String, Value> = collection
+        .variable
+        .iter()
+        .filter(|v| !v.disabled)
+        .map(|v| (variable_name(&v.key), variable_value(&v.value)))
+        .collect();
+
+    let mut environments = vec![];
+    for path in environment_paths {
+        let json = fs::read_to_string(path).context("Failed to read postman environment")?;
+        let environment: Environment =
+            serde_json::from_str(&json).context("Invalid postman environment")?;
+
+        environments.push(environment);
+    }
+
+    if environments.is_empty() {
+        create_default_variable(&mut project, collection_variables.clone())?;
+    }
+
+    // An existing variable set only gains the keys it doesn't have yet
+    for environment in environments {
+        let mut contents = collection_variables.clone();
+        for value in environment.values.iter().filter(|v| v.enabled) {
+            contents.insert(variable_name(&value.key), variable_value(&value.value));
+        }
+
+        let name = super::sanitize_name(&environment.name);
+        match project.variables()?.iter().any(|v| v.name == name) {
+            true => project.select_variable(&name)?,
+            false => project.create_variable(&name)?,
+        };
+
+        let variable = project.current_variable()?;
+        for (key, value) in contents {
+            variable.contents.entry(key).or_insert(value);
+        }
+        variable.save()?;
+    }
+
+    if !collection.event.is_empty() {
+        report.warn("collection", "scripts were not converted");
+    }
+
+    let mut requests = vec![];
+    collect_requests(
+        &collection.item,
+        "",
+        collection.auth.as_ref(),
+        &mut requests,
+        &mut report,
+    );
+
+    save_templates(project, requests, &mut report)?;
+
+    Ok(report)
+}
+
+fn collect_requests(
+    items: &[Item],
+    prefix: &str,
+    inherited_auth: Option<&Auth>,
+    requests: &mut Vec<(String, TemplateRequest)>,
+    report: &mut ImportReport,
+) {
+    for item in items {
+        let name = match prefix {
+            "" => super::sanitize_name(&item.name),
+            _ => format!("{prefix}-{}", super::sanitize_name(&item.name)),
+        };
+
+        if !item.event.is_empty() {
+            report.warn(&name, "scripts were not converted");
+        }
+
+        let auth = item.auth.as_ref().or(inherited_auth);
+
+        if let Some(children) = &item.item {
+            collect_requests(children, &name, auth, requests, report);
+            continue;
+        }
+
+        match &item.request {
+            Some(request) => {
+                requests.push((name.clone(), convert_request(&name, request, auth, report)))
+            }
+            None => report.warn(&name, "item has no request"),
+        }
+    }
+}
+
+fn convert_request(
+    name: &str,
+    request: &Request,
+    inherited_auth: Option<&Auth>,
+    report: &mut ImportReport,
+) -> TemplateRequest {
+    let request = match request {
+        Request::Url(url) => {
+            return TemplateRequest {
+                url: convert_variables(url, name, report),
+                ..Default::default()
+            }
+        }
+        Request::Full(request) => request,
+    };
+
+    let url = match &request.url {
+        Some(Url::Raw(raw)) | Some(Url::Parts { raw }) => convert_variables(raw, name, report),
+        None => String::new(),
+    };
+
+    let method = request
+        .method
+        .as_deref()
+        .unwrap_or("GET")
+        .parse()
+        .unwrap_or_else(|_| {
+            report.warn(name, "invalid method, using GET");
+            Method::Get
+        });
+
+    let mut headers = HashMap::new();
+    for header in request.header.iter().filter(|h| !h.disabled) {
+        headers.insert(
+            convert_variables(&header.key, name, report),
+            convert_variables(&variable_text(&header.value), name, report),
+        );
+    }
+
+    if let Some(auth) = request.auth.as_ref().or(inherited_auth) {
+        convert_auth(name, auth, &mut headers, report);
+    }
+
+    let mut template_request = TemplateRequest {
+        url,
+        method,
+        ..Default::default()
+    };
+
+    match &request.body {
+        Some(body) if body.mode.as_deref() == Some("formdata") => {
+            template_request.multipart = Some(convert_form_data(name, &body.formdata, report))
+        }
+        Some(body) if body.mode.as_deref() == Some("raw") => {
+            let raw = convert_variables(body.raw.as_deref().unwrap_or_default(), name, report);
+
+            match serde_json::from_str::<Value>(&raw) {
+                _ if raw.is_empty() => {}
+                Ok(json @ (Value::Object(_) | Value::Array(_))) => {
+                    template_request.body = Some(json)
+                }
+                _ => template_request.raw = Some(convert_raw(body, raw)),
+            }
+        }
+        Some(body) => template_request.body = convert_body(name, body, report),
+        None => {}
+    };
+
+    template_request.headers = headers;
+
+    template_request
+}
+
+fn convert_raw(body: &Body, raw: String) -> RawBody {
+    let language = body
+        .options
+        .as_ref()
+        .and_then(|o| o["raw"]["language"].as_str());
+
+    let content_type = match language {
+        Some("json") => "application/json",
+        Some("xml") => "application/xml",
+        Some("html") => "text/html",
+        Some("javascript") => "application/javascript",
+        _ => "text/plain",
+    };
+
+    RawBody {
+        content_type: Some(content_type.into()),
+        data: Some(raw),
+        file: None,
+    }
+}
+
+fn convert_form_data(
+    name: &str,
+    form_data: &[FormData],
+    report: &mut ImportReport,
+) -> Vec<FormPart> {
+    let mut parts = vec![];
+
+    for field in form_data.iter().filter(|f| !f.disabled) {
+        let key = convert_variables(&field.key, name, report);
+
+        let (value, file) = match field.form_type.as_deref() {
+            Some("file") => {
+                let src = match &field.src {
+                    Value::Array(src) => src.first().cloned().unwrap_or_default(),
+                    src => src.clone(),
+                };
+
+                if src.is_null() {
+                    report.warn(name, &format!("form file {key} has no source"));
+                }
+
+                let src = convert_variables(&variable_text(&src), name, report);
+
+                (None, Some(PathBuf::from(src)))
+            }
+            _ => {
+                let value = convert_variables(&variable_text(&field.value), name, report);
+
+                (Some(value), None)
+            }
+        };
+
+        parts.push(FormPart {
+            name: key,
+            value,
+            file,
+            content_type: field.content_type.clone(),
+        });
+    }
+
+    parts
+}
+
+fn convert_body(name: &str, body: &Body, report: &mut ImportReport) -> Option<Value> {
+    match body.mode.as_deref() {
+        Some("urlencoded") => {
+            let data: Vec<_> = body
+                .urlencoded
+                .iter()
+                .filter(|kv| !kv.disabled)
+                .map(|kv| format!("{}={}", kv.key, variable_text(&kv.value)))
+                .collect();
+
+            Some(Value::String(convert_variables(
+                &data.join("&"),
+                name,
+                report,
+            )))
+        }
+        Some("graphql") => {
+            let graphql = body.graphql.clone().unwrap_or_default();
+            let variables = graphql["variables"]
+                .as_str()
+                .and_then(|v| serde_json::from_str::<Value>(v).ok())
+                .unwrap_or(Value::Null);
+            let json = json!({ "query": graphql["query"], "variables": variables });
+            let json = convert_variables(&json.to_string(), name, report);
+
+            serde_json::from_str(&json).ok()
+        }
+        Some(mode) => {
+            report.warn(name, &format!("body mode {mode} was not converted"));
+
+            None
+        }
+        None => None,
+    }
+}
+
+fn convert_auth(
+    name: &str,
+    auth: &Auth,
+    headers: &mut HashMap<String, String>,
+    report: &mut ImportReport,
+) {
+    let param = |key: &str| -> Option<String> {
+        auth.params
+            .get(&auth.auth_type)
+            .and_then(|params| params.as_array())
+            .and_then(|params| params.iter().find(|p| p["key"] == key))
+            .map(|p| variable_text(&p["value"]))
+    };
+
+    match auth.auth_type.as_str() {
+        "noauth" | "inherit" => {}
+        "bearer" => {
+            let token = convert_variables(&param("token").unwrap_or_default(), name, report);
+            headers.insert("Authorization".into(), format!("Bearer {token}"));
+        }
+        "basic" => {
+            let credentials = format!(
+                "{}:{}",
+                param("username").unwrap_or_default(),
+                param("password").unwrap_or_default()
+            );
+
+            if credentials.contains("{{") {
+                report.warn(name, "basic auth using variables was not converted");
+
+                return;
+            }
+
+            let credentials = STANDARD.encode(credentials);
+            headers.insert("Authorization".into(), format!("Basic {credentials}"));
+        }
+        "apikey" if param("in").as_deref() != Some("query") => {
+            let key = convert_variables(&param("key").unwrap_or_default(), name, report);
+            let value = convert_variables(&param("value").unwrap_or_default(), name, report);
+            headers.insert(key, value);
+        }
+        auth_type => report.warn(name, &format!("auth type {auth_type} was not converted")),
+    }
+}
+
+fn convert_variables(text: &str, name: &str, report: &mut ImportReport) -> String {
+    let regex =
+        Regex::new(r"\{\{\s*([^{}]+?)\s*\}\}").expect("Failed building postman variable regex");
+
+    regex
+        .replace_all(text, |captures: &Captures| match &captures[1] {
+            "$guid" | "$randomUUID" => "{{gen:uuid}}".to_string(),
+            "$timestamp" => "{{gen:timestamp}}".to_string(),
+            v if v.starts_with('$') => {
+                report.warn(name, &format!("dynamic variable {v} was not converted"));
+
+                captures[0].to_string()
+            }
+            v => format!("{{{{{}}}}}", variable_name(v)),
+        })
+        .to_string()
+}
+
+fn variable_value(value: &Value) -> Value {
+    Value::String(variable_text(value))
+}
+
+fn variable_text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.to_string(),
+        Value::Null => String::new(),
+        v => v.to_string(),
+    }
+}
@@ -2,7 +2,7 @@ use std::{
     collections::HashMap,
     fs::{self, OpenOptions},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
 };
 
 use anyhow::{anyhow, Context, Result};
@@ -10,11 +10,18 @@ use dialoguer::Editor;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::http::Method;
+use crate::http::{FormPart, Method, RetryOn};
 
-use self::project::Project;
+use self::{expect::Expectation, project::Project, variable::Variable};
 
+pub mod cookie;
+pub mod expect;
+pub mod export;
+pub mod flow;
+pub mod import;
+pub mod path;
 pub mod project;
+pub mod settings;
 mod variable;
 
 #[derive(Serialize, Deserialize)]
@@ -23,6 +30,93 @@ pub struct TemplateRequest {
     pub method: Method,
     pub headers: HashMap<String, String>,
     pub body: Option<Value>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_file: Option<PathBuf>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub raw: Option<RawBody>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multipart: Option<Vec<FormPart>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redirect: Option<RedirectSettings>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry: Option<RetrySettings>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expect: Option<Expectation>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RedirectSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub follow: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RetrySettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<u32>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_delay: Option<f64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub on: Option<Vec<RetryOn>>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RawBody {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<PathBuf>,
+}
+
+impl RawBody {
+    pub fn read(&self) -> Result<Vec<u8>> {
+        match (&self.data, &self.file) {
+            (Some(data), None) => Ok(data.as_bytes().to_vec()),
+            (None, Some(file)) => {
+                fs::read(file).context(format!("Failed to read raw body file {}", file.display()))
+            }
+            _ => Err(anyhow!("Raw body must have either data or a file")),
+        }
+    }
+}
+
+impl TemplateRequest {
+    pub fn resolve_file_paths(&mut self, base: &Path) {
+        if let Some(body_file) = &self.body_file {
+            self.body_file = Some(base.join(body_file));
+        }
+
+        if let Some(RawBody {
+            file: Some(file), ..
+        }) = &mut self.raw
+        {
+            *file = base.join(&file);
+        }
+
+        for part in self.multipart.iter_mut().flatten() {
+            if let Some(file) = &part.file {
+                part.file = Some(base.join(file));
+            }
+        }
+    }
 }
 
 impl Default for TemplateRequest {
@@ -32,6 +126,12 @@ impl Default for TemplateRequest {
             method: Method::Get,
             headers: HashMap::default(),
             body: None,
+            body_file: None,
+            raw: None,
+            multipart: None,
+            redirect: None,
+            retry: None,
+            expect: None,
         }
     }
 }
@@ -68,7 +168,8 @@ impl Template {
     pub fn list(project: &Project) -> Result<Vec<String>> {
         let mut template_names = vec![];
         for file in fs::read_dir(&project.path)?.flatten() {
-            if !file.path().is_file() {
+            // Dotfiles like the project settings aren't templates
+            if !file.path().is_file() || file.file_name().to_string_lossy().starts_with('.') {
                 continue;
             }
 
@@ -150,12 +251,19 @@ impl Template {
         fs::remove_file(self.path).context(format!("Failed to delete template {}", self.name))
     }
 
-    pub fn request_with_variables(&mut self) -> Result<TemplateRequest> {
+    pub fn request_with_variables(&mut self, edit: bool) -> Result<TemplateRequest> {
         let json = serde_json::to_string_pretty(&self.request)?;
-        let json = self
-            .project
-            .current_variable()?
-            .replace_template_string(json)?;
+        let variable = self.project.current_variable()?;
+
+        if !edit {
+            variable.ensure_input_variables(&json)?;
+        }
+
+        let json = variable.replace_template_json(json)?;
+
+        if !edit {
+            return serde_json::from_str(&json).context("Failed to parse request");
+        }
 
         let request_edit = Editor::new()
             .extension(".json")
@@ -165,6 +273,32 @@ impl Template {
         serde_json::from_str(&request_edit).context("Failed to parse edited request")
     }
 
+    pub fn has_output_variables(&self) -> Result<bool> {
+        let json = serde_json::to_string(&self.request)?;
+
+        Ok(Variable::has_output_variables(&json))
+    }
+
+    pub fn read_body_file(&mut self, path: &Path, with_variables: bool) -> Result<Value> {
+        let contents = fs::read_to_string(path)
+            .context(format!("Failed to read body file {}", path.display()))?;
+
+        // Variables inside a json file are escaped so the file stays valid json
+        let contents = match with_variables {
+            true if serde_json::from_str::<Value>(&contents).is_ok() => self
+                .project
+                .current_variable()?
+                .replace_template_json(contents)?,
+            true => self
+                .project
+                .current_variable()?
+                .replace_template_string(contents)?,
+            false => contents,
+        };
+
+        Ok(parse_body(contents))
+    }
+
     pub fn new(project: Project, template_name: &str) -> Self {
         Self {
             name: template_name.to_string(),
@@ -174,3 +308,10 @@ impl Template {
         }
     }
 }
+
+pub fn parse_body(body: String) -> Value {
+    match serde_json::from_str::<Value>(&body) {
+        Ok(json @ (Value::Object(_) | Value::Array(_))) => json,
+        _ => Value::String(body),
+    }
+}
@@ -0,0 +1,695 @@
+use std::{borrow::Cow, cmp::Ordering, str::FromStr};
+
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde_json::Value;
+
+// Accepts jsonpath ($.items[*].id), a jq subset (.items[].id) and the dotted form (items.0.id),
+// optionally ending with length()
+#[derive(Clone, Debug)]
+pub struct Path {
+    segments: Vec<Segment>,
+    length: bool,
+}
+
+#[derive(Clone, Debug)]
+enum Segment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+    Slice(Option<i64>, Option<i64>, i64),
+    Filter(Filter),
+    Descendants,
+}
+
+#[derive(Clone, Debug)]
+enum Filter {
+    Or(Box<Filter>, Box<Filter>),
+    And(Box<Filter>, Box<Filter>),
+    Not(Box<Filter>),
+    Exists(Path),
+    Compare(Operand, Operator, Operand),
+}
+
+#[derive(Clone, Debug)]
+enum Operand {
+    Path(Path),
+    Literal(Value),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Operator {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Matches,
+}
+
+impl Path {
+    pub fn is_definite(&self) -> bool {
+        self.segments
+            .iter()
+            .all(|s| matches!(s, Segment::Key(_) | Segment::Index(_)))
+    }
+
+    pub fn query<'a>(&self, value: &'a Value) -> Vec<Cow<'a, Value>> {
+        let nodes = self.segments.iter().fold(vec![value], |nodes, segment| {
+            nodes
+                .into_iter()
+                .flat_map(|node| segment.apply(node))
+                .collect()
+        });
+
+        if !self.length {
+            return nodes.into_iter().map(Cow::Borrowed).collect();
+        }
+
+        nodes
+            .into_iter()
+            .flat_map(|node| match node {
+                Value::Array(items) => Some(items.len()),
+                Value::Object(map) => Some(map.len()),
+                Value::String(text) => Some(text.chars().count()),
+                Value::Null => Some(0),
+                _ => None,
+            })
+            .map(|len| Cow::Owned(Value::from(len)))
+            .collect()
+    }
+
+    pub fn select(&self, value: &Value) -> Value {
+        let mut matches = self.query(value);
+
+        if self.is_definite() {
+            match matches.is_empty() {
+                true => Value::Null,
+                false => matches.swap_remove(0).into_owned(),
+            }
+        } else {
+            Value::Array(matches.into_iter().map(Cow::into_owned).collect())
+        }
+    }
+}
+
+impl FromStr for Path {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s);
+        let path = parser.path(false)?;
+
+        if !parser.is_done() {
+            return Err(anyhow!(
+                "Unexpected character at position {} in path {s}",
+                parser.pos
+            ));
+        }
+
+        Ok(path)
+    }
+}
+
+impl Segment {
+    fn apply<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        match (self, value) {
+            (Self::Key(key), Value::Object(map)) => map.get(key).into_iter().collect(),
+            (Self::Key(key), Value::Array(items)) => match key.parse::<usize>() {
+                Ok(n) => items.get(n).into_iter().collect(),
+                Err(_) => vec![],
+            },
+            (Self::Index(index), Value::Array(items)) => {
+                let len = items.len() as i64;
+                let index = if *index < 0 { len + index } else { *index };
+
+                match usize::try_from(index) {
+                    Ok(index) => items.get(index).into_iter().collect(),
+                    Err(_) => vec![],
+                }
+            }
+            (Self::Wildcard, Value::Array(items)) => items.iter().collect(),
+            (Self::Wildcard, Value::Object(map)) => map.values().collect(),
+            (Self::Slice(start, end, step), Value::Array(items)) => {
+                slice(items, *start, *end, *step)
+            }
+            (Self::Filter(filter), Value::Array(items)) => {
+                items.iter().filter(|item| filter.matches(item)).collect()
+            }
+            (Self::Filter(filter), Value::Object(map)) => {
+                map.values().filter(|item| filter.matches(item)).collect()
+            }
+            (Self::Descendants, _) => {
+                let mut nodes = vec![];
+                descendants(value, &mut nodes);
+                nodes
+            }
+            _ => vec![],
+        }
+    }
+}
+
+fn descendants<'a>(value: &'a Value, nodes: &mut Vec<&'a Value>) {
+    nodes.push(value);
+
+    match value {
+        Value::Array(items) => items.iter().for_each(|item| descendants(item, nodes)),
+        Value::Object(map) => map.values().for_each(|item| descendants(item, nodes)),
+        _ => {}
+    }
+}
+
+fn slice(items: &[Value], start: Option<i64>, end: Option<i64>, step: i64) -> Vec<&Value> {
+    let len = items.len() as i64;
+    let bound = |index: i64, low: i64, high: i64| {
+        if index < 0 {
+            (len + index).clamp(low, high)
+        } else {
+            index.clamp(low, high)
+        }
+    };
+
+    let mut selected = vec![];
+    if step > 0 {
+        let (mut i, end) = (
+            bound(start.unwrap_or(0), 0, len),
+            bound(end.unwrap_or(len), 0, len),
+        );
+        while i < end {
+            selected.push(&items[i as usize]);
+            let Some(next) = i.checked_add(step) else {
+                break;
+            };
+            i = next;
+        }
+    } else if step < 0 {
+        let (mut i, end) = (
+            start.map_or(len - 1, |s| bound(s, -1, len - 1)),
+            end.map_or(-1, |e| bound(e, -1, len - 1)),
+        );
+        while i > end {
+            selected.push(&items[i as usize]);
+            let Some(next) = i.checked_add(step) else {
+                break;
+            };
+            i = next;
+        }
+    }
+
+    selected
+}
+
+impl Filter {
+    fn matches(&self, value: &Value) -> bool {
+        match self {
+            Self::Or(a, b) => a.matches(value) || b.matches(value),
+            Self::And(a, b) => a.matches(value) && b.matches(value),
+            Self::Not(f) => !f.matches(value),
+            Self::Exists(path) => !path.query(value).is_empty(),
+            Self::Compare(lhs, op, rhs) => match (lhs.resolve(value), rhs.resolve(value)) {
+                (Some(lhs), Some(rhs)) => op.compare(&lhs, &rhs),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Operand {
+    fn resolve(&self, value: &Value) -> Option<Value> {
+        match self {
+            Self::Path(path) => path.query(value).into_iter().next().map(Cow::into_owned),
+            Self::Literal(literal) => Some(literal.clone()),
+        }
+    }
+}
+
+impl Operator {
+    fn compare(&self, lhs: &Value, rhs: &Value) -> bool {
+        let ordering = match (lhs, rhs) {
+            (Value::Number(a), Value::Number(b)) => a
+                .as_f64()
+                .zip(b.as_f64())
+                .and_then(|(a, b)| a.partial_cmp(&b)),
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            (a, b) => (a == b).then_some(Ordering::Equal),
+        };
+
+        match self {
+            Self::Eq => ordering == Some(Ordering::Equal),
+            Self::Ne => ordering != Some(Ordering::Equal),
+            Self::Lt => ordering == Some(Ordering::Less),
+            Self::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            Self::Gt => ordering == Some(Ordering::Greater),
+            Self::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+            Self::Matches => match (lhs, rhs) {
+                (Value::String(text), Value::String(pattern)) => {
+                    Regex::new(pattern).is_ok_and(|re| re.is_match(text))
+                }
+                _ => false,
+            },
+        }
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+}
+
+impl Parser {
+    fn new(input: &str) -> Self {
+        Self {
+            chars: input.trim().chars().collect(),
+            pos: 0,
+        }
+    }
+
+    fn is_done(&self) -> bool {
+        self.pos >= self.chars.len()
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn peek_at(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.pos + offset).copied()
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            return true;
+        }
+
+        false
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        let matched = s
+            .chars()
+            .enumerate()
+            .all(|(i, c)| self.peek_at(i) == Some(c));
+        if matched {
+            self.pos += s.chars().count();
+        }
+
+        matched
+    }
+
+    fn expect(&mut self, c: char) -> Result<()> {
+        if self.eat(c) {
+            return Ok(());
+        }
+
+        Err(anyhow!("Expected '{c}' at position {} in path", self.pos))
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    // Relative paths start with @ and live inside filters, where whitespace and operators end them
+    fn path(&mut self, relative: bool) -> Result<Path> {
+        let mut segments = vec![];
+        let mut length = false;
+
+        if !self.eat(if relative { '@' } else { '$' }) && !relative {
+            match self.peek() {
+                Some('.' | '[') | None => {}
+                Some(_) => match self.key(relative)? {
+                    Some(segment) => segments.push(segment),
+                    None => length = true,
+                },
+            }
+        }
+
+        while !length {
+            match self.peek() {
+                Some('.') if self.peek_at(1) == Some('.') => {
+                    self.pos += 2;
+                    segments.push(Segment::Descendants);
+                    match self.peek() {
+                        Some('[') => {}
+                        Some('*') => {
+                            self.pos += 1;
+                            segments.push(Segment::Wildcard);
+                        }
+                        _ => segments.push(Segment::Key(self.name(relative)?)),
+                    }
+                }
+                Some('.') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some('[') | None => {}
+                        Some('*') => {
+                            self.pos += 1;
+                            segments.push(Segment::Wildcard);
+                        }
+                        Some(c) if relative && Self::ends_relative(c) => {}
+                        _ => match self.key(relative)? {
+                            Some(segment) => segments.push(segment),
+                            None => length = true,
+                        },
+                    }
+                }
+                Some('[') => {
+                    self.pos += 1;
+                    segments.push(self.bracket()?);
+                }
+                _ => break,
+            }
+        }
+
+        Ok(Path { segments, length })
+    }
+
+    // Returns None for the length() function, which has to end the path
+    fn key(&mut self, relative: bool) -> Result<Option<Segment>> {
+        let name = self.name(relative)?;
+        if self.peek() != Some('(') {
+            return Ok(Some(Segment::Key(name)));
+        }
+
+        match name.as_str() {
+            "length" if self.eat_str("()") => Ok(None),
+            _ => Err(anyhow!(
+                "Unknown path function {name} at position {} in path",
+                self.pos
+            )),
+        }
+    }
+
+    fn ends_relative(c: char) -> bool {
+        c.is_whitespace() || "=!<>~&|)".contains(c)
+    }
+
+    fn name(&mut self, relative: bool) -> Result<String> {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == '.' || c == '[' || c == '(' || (relative && Self::ends_relative(c)) {
+                break;
+            }
+            self.pos += 1;
+        }
+
+        if start == self.pos {
+            return Err(anyhow!("Expected a key at position {start} in path"));
+        }
+
+        Ok(self.chars[start..self.pos].iter().collect())
+    }
+
+    fn bracket(&mut self) -> Result<Segment> {
+        self.skip_whitespace();
+
+        let segment = match self.peek() {
+            Some(']') => Segment::Wildcard,
+            Some('*') => {
+                self.pos += 1;
+                Segment::Wildcard
+            }
+            Some(quote @ ('\'' | '"')) => {
+                self.pos += 1;
+                Segment::Key(self.quoted(quote)?)
+            }
+            Some('?') => {
+                self.pos += 1;
+                self.skip_whitespace();
+                self.expect('(')?;
+                let filter = self.or()?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                Segment::Filter(filter)
+            }
+            _ => self.index_or_slice()?,
+        };
+
+        self.skip_whitespace();
+        self.expect(']')?;
+
+        Ok(segment)
+    }
+
+    // Only an escaped quote is unescaped, other escapes are kept as written so
+    // regex literals like /^\d+$/ stay intact
+    fn quoted(&mut self, quote: char) -> Result<String> {
+        let mut text = String::new();
+
+        loop {
+            match self.peek() {
+                Some('\\') => {
+                    self.pos += 1;
+                    match self.peek() {
+                        Some(c) if c == quote => text.push(c),
+                        Some(c) => {
+                            text.push('\\');
+                            text.push(c);
+                        }
+                        None => return Err(anyhow!("Unterminated string in path")),
+                    }
+                    self.pos += 1;
+                }
+                Some(c) if c == quote => {
+                    self.pos += 1;
+                    return Ok(text);
+                }
+                Some(c) => {
+                    text.push(c);
+                    self.pos += 1;
+                }
+                None => return Err(anyhow!("Unterminated string in path")),
+            }
+        }
+    }
+
+    fn integer(&mut self) -> Result<Option<i64>> {
+        self.skip_whitespace();
+
+        let start = self.pos;
+        self.eat('-');
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.pos += 1;
+        }
+
+        if start == self.pos {
+            return Ok(None);
+        }
+
+        let number: String = self.chars[start..self.pos].iter().collect();
+        let number = number
+            .parse()
+            .context(format!("Invalid number {number} in path"))?;
+
+        self.skip_whitespace();
+
+        Ok(Some(number))
+    }
+
+    fn index_or_slice(&mut self) -> Result<Segment> {
+        let start = self.integer()?;
+        if !self.eat(':') {
+            return start.map(Segment::Index).ok_or(anyhow!(
+                "Expected an index at position {} in path",
+                self.pos
+            ));
+        }
+
+        let end = self.integer()?;
+        let step = if self.eat(':') { self.integer()? } else { None };
+
+        Ok(Segment::Slice(start, end, step.unwrap_or(1)))
+    }
+
+    fn or(&mut self) -> Result<Filter> {
+        let mut filter = self.and()?;
+
+        loop {
+            self.skip_whitespace();
+            if !self.eat_str("||") {
+                return Ok(filter);
+            }
+            filter = Filter::Or(Box::new(filter), Box::new(self.and()?));
+        }
+    }
+
+    fn and(&mut self) -> Result<Filter> {
+        let mut filter = self.comparison()?;
+
+        loop {
+            self.skip_whitespace();
+            if !self.eat_str("&&") {
+                return Ok(filter);
+            }
+            filter = Filter::And(Box::new(filter), Box::new(self.comparison()?));
+        }
+    }
+
+    fn comparison(&mut self) -> Result<Filter> {
+        self.skip_whitespace();
+
+        if self.peek() == Some('!') && self.peek_at(1) != Some('=') {
+            self.pos += 1;
+            return Ok(Filter::Not(Box::new(self.comparison()?)));
+        }
+
+        if self.eat('(') {
+            let filter = self.or()?;
+            self.skip_whitespace();
+            self.expect(')')?;
+            return Ok(filter);
+        }
+
+        let lhs = self.operand()?;
+        self.skip_whitespace();
+
+        let operator = [
+            ("==", Operator::Eq),
+            ("!=", Operator::Ne),
+            ("<=", Operator::Le),
+            (">=", Operator::Ge),
+            ("=~", Operator::Matches),
+            ("<", Operator::Lt),
+            (">", Operator::Gt),
+        ]
+        .into_iter()
+        .find(|(symbol, _)| self.eat_str(symbol));
+
+        match (operator, lhs) {
+            (Some((_, operator)), lhs) => Ok(Filter::Compare(lhs, operator, self.operand()?)),
+            (None, Operand::Path(path)) => Ok(Filter::Exists(path)),
+            (None, Operand::Literal(_)) => Err(anyhow!(
+                "Expected a comparison at position {} in path",
+                self.pos
+            )),
+        }
+    }
+
+    fn operand(&mut self) -> Result<Operand> {
+        self.skip_whitespace();
+
+        match self.peek() {
+            Some('@') => Ok(Operand::Path(self.path(true)?)),
+            Some(quote @ ('\'' | '"')) => {
+                self.pos += 1;
+                Ok(Operand::Literal(Value::String(self.quoted(quote)?)))
+            }
+            Some('/') => {
+                self.pos += 1;
+                Ok(Operand::Literal(Value::String(self.quoted('/')?)))
+            }
+            _ => {
+                let start = self.pos;
+                while self
+                    .peek()
+                    .is_some_and(|c| c.is_alphanumeric() || "-+.".contains(c))
+                {
+                    self.pos += 1;
+                }
+
+                let literal: String = self.chars[start..self.pos].iter().collect();
+                serde_json::from_str(&literal)
+                    .map(Operand::Literal)
+                    .map_err(|_| anyhow!("Invalid value '{literal}' at position {start} in path"))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn select(path: &str, value: &Value) -> Value {
+        path.parse::<Path>().unwrap().select(value)
+    }
+
+    #[test]
+    fn regex_filter_keeps_escapes() {
+        let value = json!({ "items": [{ "id": "123" }, { "id": "a1" }] });
+
+        assert_eq!(
+            select(r"$.items[?(@.id =~ /^\d+$/)].id", &value),
+            json!(["123"])
+        );
+        assert_eq!(
+            select(r"$.items[?(@.id =~ /^a\/?1$/)].id", &value),
+            json!(["a1"])
+        );
+    }
+
+    #[test]
+    fn string_literal_unescapes_only_the_quote() {
+        let value = json!({ "items": [{ "name": "it's" }, { "name": r"a\b" }] });
+
+        assert_eq!(
+            select(r"$.items[?(@.name == 'it\'s')].name", &value),
+            json!(["it's"])
+        );
+        assert_eq!(
+            select(r#"$.items[?(@.name == "a\b")].name"#, &value),
+            json!([r"a\b"])
+        );
+    }
+
+    #[test]
+    fn slices() {
+        let value = json!({ "x": [0, 1, 2, 3, 4] });
+
+        assert_eq!(select("$.x[1:3]", &value), json!([1, 2]));
+        assert_eq!(select("$.x[-2:]", &value), json!([3, 4]));
+        assert_eq!(select("$.x[::2]", &value), json!([0, 2, 4]));
+        assert_eq!(select("$.x[::-1]", &value), json!([4, 3, 2, 1, 0]));
+        assert_eq!(select("$.x[3:0:-2]", &value), json!([3, 1]));
+    }
+
+    #[test]
+    fn slices_with_huge_steps() {
+        let value = json!({ "x": [0, 1, 2, 3, 4] });
+
+        assert_eq!(select("$.x[4:5:9223372036854775807]", &value), json!([4]));
+        assert_eq!(select("$.x[0:5:-9223372036854775808]", &value), json!([]));
+        assert_eq!(select("$.x[4::-9223372036854775808]", &value), json!([4]));
+    }
+
+    #[test]
+    fn quoted_keys() {
+        let value = json!({ "content-type": "json", "a b": { "c.d": 1 } });
+
+        assert_eq!(select("$['content-type']", &value), json!("json"));
+        assert_eq!(select(r#"$["a b"]['c.d']"#, &value), json!(1));
+    }
+
+    #[test]
+    fn dotted_and_jq_forms() {
+        let value = json!({ "items": [{ "id": 1 }, { "id": 2 }] });
+
+        assert_eq!(select("items.0.id", &value), json!(1));
+        assert_eq!(select("items.1.id", &value), json!(2));
+        assert_eq!(select(".items[].id", &value), json!([1, 2]));
+        assert_eq!(select("$.items[-1].id", &value), json!(2));
+    }
+
+    #[test]
+    fn length() {
+        let value = json!({ "items": [1, 2, 3], "name": "abc", "empty": null });
+
+        assert_eq!(select("$.items.length()", &value), json!(3));
+        assert_eq!(select("$.name.length()", &value), json!(3));
+        assert_eq!(select("$.empty.length()", &value), json!(0));
+        assert_eq!(select("items.length()", &value), json!(3));
+    }
+
+    #[test]
+    fn missing_values() {
+        let value = json!({ "items": [] });
+
+        assert_eq!(select("$.missing", &value), Value::Null);
+        assert_eq!(select("$.items[*].id", &value), json!([]));
+    }
+}
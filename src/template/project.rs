@@ -1,5 +1,7 @@
 use std::{fs, path::PathBuf};
 
+use crate::http::HttpResponse;
+
 use super::variable::Variable;
 use anyhow::{anyhow, Context, Result};
 
@@ -103,11 +105,11 @@ impl Project {
         Ok(())
     }
 
-    pub fn update_variables_from_response_body(&mut self, response_body: &str) -> Result<()> {
+    pub fn update_variables_from_response(&mut self, response: &HttpResponse) -> Result<()> {
         self.load_variables()?;
 
         for var in &mut self.variables {
-            var.update_from_response_body(response_body)?;
+            var.update_from_response(response)?;
         }
 
         Ok(())
@@ -134,6 +136,18 @@ impl Project {
         })
     }
 
+    pub fn flows_path(&self) -> PathBuf {
+        self.path.join("flows")
+    }
+
+    pub fn cookies_path(&self) -> PathBuf {
+        self.path.join("cookies")
+    }
+
+    pub fn settings_path(&self) -> PathBuf {
+        self.path.join(".settings.json")
+    }
+
     fn variables_path(&self) -> PathBuf {
         self.path.join("variables")
     }
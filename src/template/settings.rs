@@ -0,0 +1,108 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::{anyhow, Context, Result};
+use dialoguer::Editor;
+use serde::{Deserialize, Serialize};
+
+use crate::http::{ProxySettings, TlsSettings};
+
+use super::project::Project;
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ConnectionSettings {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proxy: Option<ProxySettings>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<TlsSettings>,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+pub struct ProjectSettings {
+    #[serde(skip)]
+    path: PathBuf,
+
+    #[serde(flatten)]
+    pub connection: ConnectionSettings,
+
+    // Overrides for a variable set, like a staging CA, replace the project settings
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variables: HashMap<String, ConnectionSettings>,
+}
+
+impl ProjectSettings {
+    pub fn get(project: &Project) -> Result<Self> {
+        let path = project.settings_path();
+        if !path.is_file() {
+            return Ok(Self {
+                path,
+                ..Default::default()
+            });
+        }
+
+        let json = fs::read_to_string(&path).context(format!(
+            "Failed to read settings of project {}",
+            project.name
+        ))?;
+
+        Ok(Self {
+            path,
+            ..serde_json::from_str(&json)?
+        })
+    }
+
+    pub fn connection(&self, variable_name: &str) -> ConnectionSettings {
+        let mut connection = self.connection.clone();
+
+        if let Some(overrides) = self.variables.get(variable_name) {
+            if overrides.proxy.is_some() {
+                connection.proxy = overrides.proxy.clone();
+            }
+
+            if overrides.tls.is_some() {
+                connection.tls = overrides.tls.clone();
+            }
+        }
+
+        if let (Some(tls), Some(project_path)) = (&mut connection.tls, self.path.parent()) {
+            tls.resolve_file_paths(project_path);
+        }
+
+        connection
+    }
+
+    pub fn save(&mut self) -> Result<&mut Self> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+
+        let json = serde_json::to_string(&self)?;
+
+        file.write_all(json.as_bytes())
+            .context("Failed to save project settings")?;
+
+        Ok(self)
+    }
+
+    pub fn edit(&mut self) -> Result<&mut Self> {
+        let json = serde_json::to_string_pretty(&self)?;
+
+        let settings_edit = Editor::new()
+            .extension(".json")
+            .edit(&json)?
+            .ok_or(anyhow!("Failed to edit project settings"))?;
+
+        let settings: Self = serde_json::from_str(&settings_edit)?;
+        self.connection = settings.connection;
+        self.variables = settings.variables;
+
+        Ok(self)
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    borrow::Cow,
     collections::HashMap,
     fs::{self, OpenOptions},
     path::PathBuf,
@@ -8,9 +9,14 @@ use anyhow::{anyhow, Context, Result};
 use chrono::Utc;
 use dialoguer::Editor;
 use regex::Regex;
+use reqwest::header::SET_COOKIE;
 use serde_json::Value;
 use uuid::Uuid;
 
+use crate::http::HttpResponse;
+
+use super::path::Path;
+
 type TemplateVariable = HashMap<String, Value>;
 
 pub struct Variable {
@@ -30,6 +36,7 @@ impl Variable {
         let file = OpenOptions::new()
             .create(true)
             .write(true)
+            .truncate(true)
             .open(&variable.path)?;
 
         serde_json::to_writer(file, &variable.contents)?;
@@ -53,25 +60,77 @@ impl Variable {
             contents,
         })
     }
-    pub fn replace_template_string(&self, template_json: String) -> Result<String> {
+    pub fn replace_template_string(&self, template: String) -> Result<String> {
+        self.replace_variables(template, false)
+    }
+
+    // Values are escaped so they stay valid inside the strings of a json template
+    pub fn replace_template_json(&self, template_json: String) -> Result<String> {
+        self.replace_variables(template_json, true)
+    }
+
+    fn replace_variables(&self, template: String, escape_json: bool) -> Result<String> {
         let template_variables =
-            Self::get_variables_from_string(Self::any_variable_regex(), &template_json);
+            Self::get_variables_from_string(Self::any_variable_regex(), &template);
 
-        let mut replaced = template_json;
+        let mut replaced = template;
         for variable in template_variables {
             let value = Self::parse_template_variable(&variable, self.contents.get(&variable))
-                .ok_or(anyhow!("Template variable not found"))?;
+                .ok_or(anyhow!("Template variable {variable} not found"))?;
+
+            let value = match value {
+                Value::String(value) => value,
+                Value::Number(_) | Value::Bool(_) => value.to_string(),
+                _ => {
+                    return Err(anyhow!(
+                        "Failed to parse template variable {variable} value"
+                    ))
+                }
+            };
 
-            let value = value
-                .as_str()
-                .ok_or(anyhow!("Failed to parse template variable value"))?;
+            let value = match escape_json {
+                true => {
+                    let quoted = Value::String(value).to_string();
+                    quoted[1..quoted.len() - 1].to_string()
+                }
+                false => value,
+            };
 
-            replaced = replaced.replace(format!("{{{{{}}}}}", variable).as_str(), value);
+            replaced = replaced.replace(format!("{{{{{}}}}}", variable).as_str(), &value);
         }
 
         Ok(replaced)
     }
 
+    // Saving a template registers its variables with an empty value, without an editor
+    // to fill them in an empty input variable is as good as a missing one
+    pub fn ensure_input_variables(&self, template: &str) -> Result<()> {
+        let mut missing: Vec<_> =
+            Self::get_variables_from_string(Self::input_variable_regex(), template)
+                .into_iter()
+                .filter(|variable| {
+                    !matches!(self.contents.get(variable), Some(value) if value.as_str() != Some(""))
+                })
+                .collect();
+        missing.sort();
+        missing.dedup();
+
+        match missing.is_empty() {
+            true => Ok(()),
+            false => Err(anyhow!(
+                "Template variable(s) {} have no value in variable set {}",
+                missing.join(", "),
+                self.name
+            )),
+        }
+    }
+
+    pub fn has_output_variables(template: &str) -> bool {
+        Self::get_variables_from_string(Self::output_variable_regex(), template)
+            .iter()
+            .any(|variable| variable.starts_with("out:"))
+    }
+
     pub fn update_from_template_string(&mut self, template_string: &str) -> Result<()> {
         let input_variables =
             Self::get_variables_from_string(Self::input_variable_regex(), template_string);
@@ -85,7 +144,7 @@ impl Variable {
             .collect();
 
         for var in variables {
-            if self.contents.get(&var).is_none() {
+            if !self.contents.contains_key(&var) {
                 self.contents
                     .insert(var.to_string(), Value::String("".into()));
             }
@@ -94,36 +153,81 @@ impl Variable {
         self.save()
     }
 
-    pub fn update_from_response_body(&mut self, response_body: &str) -> Result<()> {
-        let response_json: Value = serde_json::from_str(response_body)?;
+    pub fn update_from_response(&mut self, response: &HttpResponse) -> Result<()> {
+        let body: Option<Value> = serde_json::from_str(&response.body).ok();
+
         for (out_var, out_value) in self.contents.iter_mut() {
-            if !out_var.starts_with("out:") {
+            let Some(output) = out_var.strip_prefix("out:") else {
                 continue;
             };
 
-            let value = out_var
-                .strip_prefix("out:")
-                .ok_or(anyhow!("Invalid output variable declaration"))?
-                .split('.')
-                .fold(&response_json, |acc, path_item| {
-                    if acc.is_array() {
-                        if let Ok(n) = path_item.parse::<usize>() {
-                            return &acc[n];
-                        }
-                    }
-
-                    &acc[path_item]
-                });
+            let value = if output == "status" {
+                Value::from(response.status.as_u16())
+            } else if let Some(name) = output.strip_prefix("header.") {
+                Self::header_value(response, name)
+            } else if let Some(name) = output.strip_prefix("cookie.") {
+                Self::cookie_value(response, name)
+            } else if let Some(body) = &body {
+                let path: Path = output
+                    .parse()
+                    .context(format!("Invalid output variable {out_var}"))?;
+                Self::body_value(&path, body)
+            } else {
+                continue;
+            };
 
             match value {
-                Value::Null | Value::Array(_) | Value::Object(_) => continue,
-                _ => *out_value = value.clone(),
+                Value::Null => continue,
+                Value::Array(_) | Value::Object(_) => *out_value = Value::String(value.to_string()),
+                _ => *out_value = value,
             };
         }
 
         self.save()
     }
 
+    fn header_value(response: &HttpResponse, name: &str) -> Value {
+        let values: Vec<_> = response
+            .headers
+            .get_all(name)
+            .iter()
+            .map(|v| String::from_utf8_lossy(v.as_bytes()).into_owned())
+            .collect();
+
+        match values.is_empty() {
+            true => Value::Null,
+            false => Value::String(values.join(", ")),
+        }
+    }
+
+    // Login endpoints often set the cookie on a redirect, so every hop is searched
+    // and the latest value wins
+    fn cookie_value(response: &HttpResponse, name: &str) -> Value {
+        response
+            .redirects
+            .iter()
+            .map(|redirect| &redirect.headers)
+            .chain([&response.headers])
+            .flat_map(|headers| headers.get_all(SET_COOKIE))
+            .filter_map(|header| header.to_str().ok())
+            .filter_map(|cookie| cookie.split(';').next()?.split_once('='))
+            .rfind(|(cookie_name, _)| cookie_name.trim() == name)
+            .map_or(Value::Null, |(_, value)| {
+                Value::String(value.trim().trim_matches('"').to_string())
+            })
+    }
+
+    // Paths with wildcards or filters capture a single match as is and several as an array
+    fn body_value(path: &Path, body: &Value) -> Value {
+        let mut matches = path.query(body);
+
+        match (path.is_definite(), matches.len()) {
+            (_, 0) => Value::Null,
+            (true, _) | (false, 1) => matches.swap_remove(0).into_owned(),
+            (false, _) => Value::Array(matches.into_iter().map(Cow::into_owned).collect()),
+        }
+    }
+
     pub fn save(&mut self) -> Result<()> {
         let file = OpenOptions::new()
             .write(true)
@@ -189,12 +293,12 @@ impl Variable {
     }
 
     fn output_variable_regex() -> Regex {
-        Regex::new(r"\{\{(out:){0,1}[\w\._-]+\}\}")
+        Regex::new(r#"\{\{(out:[^{}"]+|[\w\._-]+)\}\}"#)
             .expect("Failed building template output variable regex")
     }
 
     fn any_variable_regex() -> Regex {
-        Regex::new(r"\{\{(gen:){0,1}(out:){0,1}[\w\._-]+\}\}")
+        Regex::new(r#"\{\{(gen:){0,1}(out:[^{}"]+|[\w\._-]+)\}\}"#)
             .expect("Failed building template generated variable regex")
     }
 }
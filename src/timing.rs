@@ -0,0 +1,158 @@
+use std::{
+    fmt,
+    sync::{Arc, Mutex, MutexGuard, Once},
+    time::{Duration, Instant},
+};
+
+use hyper::{
+    client::connect::dns::{GaiResolver, Name},
+    service::Service,
+};
+use reqwest::dns::{Addrs, Resolve, Resolving};
+use tracing::{
+    field::{Field, Visit},
+    level_filters::LevelFilter,
+    span, Event, Level, Metadata, Subscriber,
+};
+
+// Requests are sent one at a time, so the phases of the connection opened by the
+// current attempt are kept in one place
+static PHASES: Mutex<Phases> = Mutex::new(Phases::new());
+static SUBSCRIBER: Once = Once::new();
+
+#[derive(Clone, Copy, Default)]
+pub struct ConnectionTiming {
+    pub dns: Option<Duration>,
+    pub connect: Option<Duration>,
+    pub tls: Option<Duration>,
+}
+
+struct Phases {
+    dns_start: Option<Instant>,
+    dns_end: Option<Instant>,
+    connect_start: Option<Instant>,
+    connect_end: Option<Instant>,
+    first_write: Option<Instant>,
+}
+
+impl Phases {
+    const fn new() -> Self {
+        Self {
+            dns_start: None,
+            dns_end: None,
+            connect_start: None,
+            connect_end: None,
+            first_write: None,
+        }
+    }
+}
+
+fn phases() -> MutexGuard<'static, Phases> {
+    PHASES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+fn between(start: Option<Instant>, end: Option<Instant>) -> Option<Duration> {
+    Some(end?.saturating_duration_since(start?))
+}
+
+pub fn reset() {
+    *phases() = Phases::new();
+}
+
+// Phases are only reported for a connection opened since the last reset, a pooled
+// connection has none. The tls handshake ends when the request is first written
+pub fn connection(https: bool) -> ConnectionTiming {
+    let phases = phases();
+
+    ConnectionTiming {
+        dns: between(phases.dns_start, phases.dns_end),
+        connect: between(phases.connect_start, phases.connect_end),
+        tls: match https {
+            true => between(phases.connect_end, phases.first_write),
+            false => None,
+        },
+    }
+}
+
+// Resolves like reqwest's default resolver and records how long the lookup took,
+// also starts listening for the connection events
+pub fn resolver() -> Arc<TimingResolver> {
+    SUBSCRIBER.call_once(|| {
+        let _ = tracing::subscriber::set_global_default(ConnectionEvents);
+    });
+
+    Arc::new(TimingResolver(GaiResolver::new()))
+}
+
+pub struct TimingResolver(GaiResolver);
+
+impl Resolve for TimingResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let mut resolver = self.0.clone();
+        phases().dns_start = Some(Instant::now());
+
+        Box::pin(async move {
+            let addrs = resolver.call(name).await;
+            phases().dns_end = Some(Instant::now());
+
+            Ok(Box::new(addrs?) as Addrs)
+        })
+    }
+}
+
+// hyper has no connection callbacks, its connector and writer report progress
+// through debug events instead
+struct ConnectionEvents;
+
+const CONNECTOR_TARGET: &str = "hyper::client::connect::http";
+const WRITER_TARGET: &str = "hyper::proto::h1::io";
+
+#[derive(Default)]
+struct Message(String);
+
+impl Visit for Message {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.0 = format!("{value:?}");
+        }
+    }
+}
+
+impl Subscriber for ConnectionEvents {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.is_event()
+            && *metadata.level() == Level::DEBUG
+            && [CONNECTOR_TARGET, WRITER_TARGET].contains(&metadata.target())
+    }
+
+    fn max_level_hint(&self) -> Option<LevelFilter> {
+        Some(LevelFilter::DEBUG)
+    }
+
+    fn new_span(&self, _: &span::Attributes<'_>) -> span::Id {
+        span::Id::from_u64(1)
+    }
+
+    fn record(&self, _: &span::Id, _: &span::Record<'_>) {}
+
+    fn record_follows_from(&self, _: &span::Id, _: &span::Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut message = Message::default();
+        event.record(&mut message);
+
+        let now = Some(Instant::now());
+        let mut phases = phases();
+        if message.0.starts_with("connecting to ") {
+            phases.connect_start = phases.connect_start.or(now);
+        } else if message.0.starts_with("connected to ") {
+            phases.connect_end = now;
+        } else if message.0.starts_with("flushed ") && phases.connect_end.is_some() {
+            phases.first_write = phases.first_write.or(now);
+        }
+    }
+
+    fn enter(&self, _: &span::Id) {}
+
+    fn exit(&self, _: &span::Id) {}
+}